@@ -1,16 +1,24 @@
 pub mod achievement;
 pub mod anime_effect;
 pub mod audio;
+pub mod backup;
 pub mod cursor;
 pub mod cursor_icon;
+pub mod export;
+pub mod integrity;
 pub mod interaction;
 pub mod key_binding;
 pub mod leaderboard;
+pub mod plant;
 pub mod plugin;
+pub mod score;
 pub mod screenshot;
 pub mod settings;
+pub mod shape_match;
 pub mod startup;
 pub mod status;
+pub mod stroke;
+pub mod sync;
 pub mod theme;
 pub mod timer;
 pub mod ui;
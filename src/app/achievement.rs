@@ -1,37 +1,82 @@
 use crate::app;
 use bevy::prelude::*;
 use bevy::utils::HashMap;
-use bevy_persistent::prelude::*;
+use bevy_persistent::{error::PersistenceError, prelude::*};
 use chrono::Local;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 
-pub mod perfect_match;
-pub mod time_xxx_s;
-pub mod zero_repaint;
-pub mod zero_undo;
+mod progress;
+pub mod registry;
+mod toast;
 
-pub const ACHIEVEMENTS: [&dyn AchievementDefBase; 4] = [
-    &perfect_match::AchievementDef,
-    &time_xxx_s::AchievementDef,
-    &zero_undo::AchievementDef,
-    &zero_repaint::AchievementDef,
+pub use progress::build_progress_ui;
+pub use registry::{achievements, fetch_ach_def};
+
+pub const MAX_RUNNING_ACH_COUNT: usize = 2;
+
+pub const ACHIEVEMENT_TIERS: [AchievementTier; 3] = [
+    AchievementTier::Bronze,
+    AchievementTier::Silver,
+    AchievementTier::Gold,
 ];
 
-const MAX_RUNNING_ACH_COUNT: usize = 2;
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum AchievementTier {
+    Bronze,
+    Silver,
+    Gold,
+}
+
+impl AchievementTier {
+    pub fn name(&self) -> &str {
+        match self {
+            AchievementTier::Bronze => "Bronze",
+            AchievementTier::Silver => "Silver",
+            AchievementTier::Gold => "Gold",
+        }
+    }
+    pub fn index(&self) -> usize {
+        match self {
+            AchievementTier::Bronze => 0,
+            AchievementTier::Silver => 1,
+            AchievementTier::Gold => 2,
+        }
+    }
+}
+
+pub fn next_tier(tier: Option<AchievementTier>) -> Option<AchievementTier> {
+    ACHIEVEMENT_TIERS
+        .into_iter()
+        .find(|ach_tier| Some(*ach_tier) > tier)
+        .or(tier)
+}
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct AchievementRecord {
     pub code: String,
     pub is_done: bool,
     pub done_at: String,
+    pub tier: Option<AchievementTier>,
 }
 
-#[derive(Resource, Serialize, Deserialize)]
+#[derive(Resource, Serialize, Deserialize, Clone)]
 pub struct AchievementStore {
     pub is_enabled: bool,
     pinned_codes: Vec<String>,
     record_map: HashMap<String, AchievementRecord>,
+    progress_map: HashMap<String, u32>,
+}
+
+impl Default for AchievementStore {
+    fn default() -> Self {
+        Self {
+            is_enabled: true,
+            pinned_codes: vec![],
+            record_map: HashMap::new(),
+            progress_map: HashMap::new(),
+        }
+    }
 }
 
 impl AchievementStore {
@@ -55,6 +100,44 @@ impl AchievementStore {
             self.record_map.insert(record.code.clone(), record);
         }
     }
+    pub fn mark_tier(&mut self, code: &str, tier: AchievementTier, is_done: bool) {
+        let mut record = self.fetch_record(code);
+        if Some(tier) > self.fetch_tier(code) {
+            record.tier = Some(tier);
+            record.is_done = record.is_done || is_done;
+            record.done_at = Local::now().format("%Y-%m-%d_%H:%M:%S%.9f").to_string();
+            self.record_map.insert(record.code.clone(), record);
+        }
+    }
+    pub fn add_progress(&mut self, code: &str, amount: u32) {
+        let progress = self.progress_map.entry(String::from(code)).or_insert(0);
+        *progress = progress.saturating_add(amount);
+    }
+    pub fn fetch_progress(&self, code: &str) -> u32 {
+        self.progress_map.get(code).copied().unwrap_or(0)
+    }
+    pub fn progresses(&self) -> impl Iterator<Item = (&String, &u32)> {
+        self.progress_map.iter()
+    }
+    pub fn unlocked_count(&self, except_code: &str) -> u32 {
+        self.record_map
+            .values()
+            .filter(|record| {
+                (record.is_done || record.tier.is_some())
+                    && record.code != except_code
+                    && fetch_ach_def(&record.code).is_some()
+            })
+            .count() as u32
+    }
+    pub fn fetch_tier(&self, code: &str) -> Option<AchievementTier> {
+        let record = self.record_map.get(code)?;
+        let is_tiered = fetch_ach_def(code).is_some_and(|ach_def| ach_def.tiers().is_some());
+        match record.tier {
+            Some(tier) => Some(tier),
+            None if record.is_done && is_tiered => Some(AchievementTier::Gold),
+            None => None,
+        }
+    }
     pub fn is_done(&self, code: &str) -> bool {
         self.fetch_record(code).is_done
     }
@@ -66,15 +149,63 @@ impl AchievementStore {
             self.pinned_codes.contains(&String::from(code))
         }
     }
+    pub fn fetch_ui_status(&self, code: &str) -> AchievementUiStatus {
+        if self.is_done(code) {
+            AchievementUiStatus::Done
+        } else if self.is_pinned(code) {
+            AchievementUiStatus::Pinned
+        } else {
+            AchievementUiStatus::Normal
+        }
+    }
     pub fn pinned_codes(&self) -> Vec<String> {
         self.pinned_codes.clone()
     }
+    pub fn records(&self) -> impl Iterator<Item = &AchievementRecord> {
+        self.record_map.values()
+    }
+    pub fn merge(&mut self, other: &AchievementStore) -> usize {
+        let mut merged_count = 0;
+        for (code, progress) in other.progress_map.iter() {
+            if *progress > self.fetch_progress(code) {
+                self.progress_map.insert(code.clone(), *progress);
+            }
+        }
+        for (code, record) in other.record_map.iter() {
+            if !(record.is_done || record.tier.is_some()) || fetch_ach_def(code).is_none() {
+                continue;
+            }
+            let is_newer = match self.record_map.get(code) {
+                Some(stored) => {
+                    let (tier, stored_tier) = (other.fetch_tier(code), self.fetch_tier(code));
+                    tier > stored_tier
+                        || (tier == stored_tier && record.is_done && !stored.is_done)
+                        || (tier == stored_tier
+                            && record.is_done == stored.is_done
+                            && record.done_at < stored.done_at)
+                }
+                None => true,
+            };
+            if is_newer {
+                self.record_map.insert(code.clone(), record.clone());
+                if record.is_done {
+                    self.pinned_codes.retain(|pinned_code| pinned_code != code);
+                }
+                merged_count += 1;
+            }
+        }
+        merged_count
+    }
     pub fn fetch_record(&self, code: &str) -> AchievementRecord {
         if let Some(record) = self.record_map.get(code) {
             record.clone()
         } else {
-            let ach_def = fetch_ach_def(code);
-            ach_def.build_empty_record()
+            AchievementRecord {
+                code: String::from(code),
+                is_done: false,
+                done_at: String::from(""),
+                tier: None,
+            }
         }
     }
 }
@@ -86,7 +217,7 @@ pub enum AchievementUiStatus {
     Done,
 }
 
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Deserialize, Clone, PartialEq, Debug)]
 pub enum AchievementProgressUi {
     Bar,
     Dots,
@@ -94,20 +225,20 @@ pub enum AchievementProgressUi {
 
 #[derive(Resource, Default)]
 pub struct AchievementInfo {
-    done_codes: Vec<String>,
+    done_unlocks: Vec<(String, Option<AchievementTier>)>,
     running_codes: Vec<String>,
 }
 
 impl AchievementInfo {
     pub fn reset(&mut self, store: &AchievementStore) {
+        // NOTE: keep the unlocks queued for toasts, they are shown in the next round
         self.update_running_codes(store);
-        self.done_codes = vec![];
     }
 
     pub fn update_running_codes(&mut self, store: &AchievementStore) {
         self.running_codes = vec![];
         for code in store.pinned_codes.iter() {
-            if store.is_done(code) {
+            if store.is_done(code) || fetch_ach_def(code).is_none() {
                 continue;
             }
             self.running_codes.push(String::from(code));
@@ -115,7 +246,7 @@ impl AchievementInfo {
                 return;
             }
         }
-        for ach_def in ACHIEVEMENTS {
+        for ach_def in achievements() {
             if store.is_done(ach_def.code()) || store.is_pinned(ach_def.code()) {
                 continue;
             }
@@ -134,17 +265,12 @@ impl AchievementInfo {
         self.running_codes.clone()
     }
 
-    pub fn push_to_done(&mut self, code: &str) -> Option<String> {
-        self.done_codes.insert(0, String::from(code));
-        if self.done_codes.len() == 1 {
-            self.done_codes.pop()
-        } else {
-            None
-        }
+    pub fn push_to_done(&mut self, code: &str, tier: Option<AchievementTier>) {
+        self.done_unlocks.insert(0, (String::from(code), tier));
     }
 
-    pub fn next_done(&mut self) -> Option<String> {
-        self.done_codes.pop()
+    pub fn next_done(&mut self) -> Option<(String, Option<AchievementTier>)> {
+        self.done_unlocks.pop()
     }
 }
 
@@ -152,26 +278,110 @@ pub struct AchievementPlugin;
 
 impl Plugin for AchievementPlugin {
     fn build(&self, app: &mut App) {
-        let config_dir = dirs::config_dir()
-            .map(|native_config_dir| native_config_dir.join(app::APP_CODE))
-            .unwrap_or(Path::new("local").join("configuration"));
-        app.insert_resource(
-            Persistent::<AchievementStore>::builder()
-                .name("achievement")
-                .format(StorageFormat::Bincode)
-                .path(config_dir.join("achievement.bin"))
-                .default(AchievementStore {
-                    is_enabled: true,
-                    pinned_codes: vec![],
-                    record_map: HashMap::new(),
-                })
-                .build()
+        info!("loaded {} achievements", achievements().count());
+        app::backup::insert_loaded(app, load_achievement_store());
+        app.insert_resource(AchievementInfo::default())
+            .add_systems(
+                Update,
+                (toast::handle_toast_queue, toast::handle_toast_done),
+            )
+            .add_systems(
+                PostUpdate,
+                app::backup::rotate_persisted_backups::<AchievementStore>,
+            );
+    }
+}
+
+pub fn load_achievement_store() -> app::backup::Loaded<AchievementStore> {
+    let config_dir = dirs::config_dir()
+        .map(|native_config_dir| native_config_dir.join(app::APP_CODE))
+        .unwrap_or(Path::new("local").join("configuration"));
+    let path = config_dir.join("achievement.bin");
+    match app::backup::load_with_backups(
+        "achievements",
+        &path,
+        |path| build_achievement_store(path, false),
+        migrate_achievement_store,
+    ) {
+        Ok(loaded) => loaded,
+        Err(message) => app::backup::Loaded {
+            persistent: build_achievement_store(&path, true)
                 .expect("failed to initialize variables"),
-        );
-        app.insert_resource(AchievementInfo::default());
+            notice: Some(message),
+            is_migrated: false,
+        },
     }
 }
 
+fn build_achievement_store(
+    path: &Path,
+    revert_on_errors: bool,
+) -> Result<Persistent<AchievementStore>, PersistenceError> {
+    Persistent::<AchievementStore>::builder()
+        .name("achievement")
+        .format(StorageFormat::Bincode)
+        .path(path)
+        .default(AchievementStore::default())
+        .revertible(true)
+        .revert_to_default_on_deserialization_errors(revert_on_errors)
+        .build()
+}
+
+#[derive(Resource, Serialize, Deserialize)]
+struct LegacyAchievementStore {
+    is_enabled: bool,
+    pinned_codes: Vec<String>,
+    record_map: HashMap<String, LegacyAchievementRecord>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+struct LegacyAchievementRecord {
+    code: String,
+    is_done: bool,
+    done_at: String,
+}
+
+impl From<LegacyAchievementRecord> for AchievementRecord {
+    fn from(record: LegacyAchievementRecord) -> Self {
+        Self {
+            code: record.code,
+            is_done: record.is_done,
+            done_at: record.done_at,
+            tier: None,
+        }
+    }
+}
+
+fn migrate_achievement_store(path: &Path) -> Option<Persistent<AchievementStore>> {
+    let legacy = Persistent::<LegacyAchievementStore>::builder()
+        .name("legacy achievement")
+        .format(StorageFormat::Bincode)
+        .path(path)
+        .default(LegacyAchievementStore {
+            is_enabled: true,
+            pinned_codes: vec![],
+            record_map: HashMap::new(),
+        })
+        .build()
+        .ok()?;
+    let store = AchievementStore {
+        is_enabled: legacy.is_enabled,
+        pinned_codes: legacy.pinned_codes.clone(),
+        record_map: legacy
+            .record_map
+            .iter()
+            .map(|(code, record)| (code.clone(), AchievementRecord::from(record.clone())))
+            .collect(),
+        progress_map: HashMap::new(),
+    };
+    let mut achievement_store =
+        build_achievement_store(path, true).expect("failed to initialize variables");
+    achievement_store
+        .set(store)
+        .expect("failed to migrate achievements");
+    Some(achievement_store)
+}
+
 pub trait AchievementDefBase {
     fn code(&self) -> &str;
     fn name(&self) -> &str;
@@ -180,14 +390,11 @@ pub trait AchievementDefBase {
         self.code()
     }
     fn description(&self) -> String;
-    fn check_done(&self, status: &ResMut<app::status::AppStatus>) -> (u32, u32, bool);
-    fn build_empty_record(&self) -> AchievementRecord {
-        AchievementRecord {
-            code: String::from(self.code()),
-            is_done: false,
-            done_at: String::from(""),
-        }
-    }
+    fn check_done(
+        &self,
+        status: &ResMut<app::status::AppStatus>,
+        store: &AchievementStore,
+    ) -> (u32, u32, bool);
     fn icon_path(&self) -> String {
         format!("images/achievement/{}.png", self.icon())
     }
@@ -195,13 +402,38 @@ pub trait AchievementDefBase {
     fn format_value(&self, value: u32) -> String {
         format!("{}", value)
     }
-}
-
-pub fn fetch_ach_def(code: &str) -> &dyn AchievementDefBase {
-    for ach_def in ACHIEVEMENTS {
-        if ach_def.code() == code {
-            return ach_def;
+    fn tiers(&self) -> Option<[u32; 3]> {
+        None
+    }
+    fn check_tier(
+        &self,
+        _status: &ResMut<app::status::AppStatus>,
+        _store: &AchievementStore,
+    ) -> Option<AchievementTier> {
+        None
+    }
+    fn count_round(&self, _status: &ResMut<app::status::AppStatus>) -> bool {
+        false
+    }
+    fn progress(&self, _store: &AchievementStore) -> Option<(u32, u32)> {
+        None
+    }
+    fn tier_threshold(&self, tier: Option<AchievementTier>) -> Option<u32> {
+        let tiers = self.tiers()?;
+        Some(tiers[tier.unwrap_or(AchievementTier::Bronze).index()])
+    }
+    fn tier_name(&self, tier: Option<AchievementTier>) -> String {
+        match self.tier_threshold(tier) {
+            Some(threshold) => self.name().replace("xxx", &self.format_value(threshold)),
+            None => String::from(self.name()),
+        }
+    }
+    fn tier_description(&self, tier: Option<AchievementTier>) -> String {
+        match self.tier_threshold(tier) {
+            Some(threshold) => self
+                .description()
+                .replace("xxx", &self.format_value(threshold)),
+            None => self.description(),
         }
     }
-    panic!("Invalid achievement code")
 }
@@ -0,0 +1,108 @@
+use crate::app::{
+    achievement::{AchievementDefBase, AchievementProgressUi},
+    theme::*,
+    ui,
+};
+use bevy::prelude::*;
+
+const BAR_W: f32 = 40.0;
+const BAR_H: f32 = 2.0;
+const DOT_SIZE: f32 = 2.5;
+const MAX_DOT_COUNT: u32 = 10;
+
+pub fn build_progress_ui(
+    parent: &mut ChildBuilder,
+    asset_server: &Res<AssetServer>,
+    ach_def: &dyn AchievementDefBase,
+    current: u32,
+    target: u32,
+) -> Entity {
+    parent
+        .spawn(NodeBundle {
+            style: Style {
+                align_items: AlignItems::Center,
+                column_gap: ui::px_p(3.0),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            match ach_def.progress_ui() {
+                AchievementProgressUi::Dots if target <= MAX_DOT_COUNT => {
+                    build_dots(parent, ach_def.color(), current, target)
+                }
+                _ => build_bar(parent, ach_def.color(), current, target),
+            }
+            parent.spawn(TextBundle::from_section(
+                format!(
+                    "{}/{}",
+                    ach_def.format_value(current.min(target)),
+                    ach_def.format_value(target)
+                ),
+                TextStyle {
+                    font: asset_server.load(FONT),
+                    font_size: ui::FONT_SIZE * 0.6,
+                    color: FG_COLOR,
+                },
+            ));
+        })
+        .id()
+}
+
+fn build_bar(parent: &mut ChildBuilder, color: Color, current: u32, target: u32) {
+    let ratio = if target > 0 {
+        (current as f32 / target as f32).min(1.0)
+    } else {
+        1.0
+    };
+    parent
+        .spawn(NodeBundle {
+            style: Style {
+                width: ui::px_p(BAR_W),
+                height: ui::px_p(BAR_H),
+                ..default()
+            },
+            background_color: MUTE_COLOR.into(),
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn(NodeBundle {
+                style: Style {
+                    width: Val::Percent(ratio * 100.0),
+                    height: Val::Percent(100.0),
+                    ..default()
+                },
+                background_color: color.into(),
+                ..default()
+            });
+        });
+}
+
+fn build_dots(parent: &mut ChildBuilder, color: Color, current: u32, target: u32) {
+    parent
+        .spawn(NodeBundle {
+            style: Style {
+                align_items: AlignItems::Center,
+                column_gap: ui::px_p(1.0),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            for index in 0..target {
+                parent.spawn(NodeBundle {
+                    style: Style {
+                        width: ui::px_p(DOT_SIZE),
+                        height: ui::px_p(DOT_SIZE),
+                        ..default()
+                    },
+                    background_color: if index < current {
+                        color.into()
+                    } else {
+                        MUTE_COLOR.into()
+                    },
+                    ..default()
+                });
+            }
+        });
+}
@@ -0,0 +1,289 @@
+use crate::app::{
+    achievement::{
+        AchievementDefBase, AchievementProgressUi, AchievementStore, AchievementTier,
+        ACHIEVEMENT_TIERS,
+    },
+    status::{AppStatus, StatusField},
+};
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::sync::OnceLock;
+
+const BUILTIN_DEFS: [(&str, &str); 8] = [
+    (
+        "achievement_xxx.ron",
+        include_str!("../../../assets/achievements/achievement_xxx.ron"),
+    ),
+    (
+        "perfect_match.ron",
+        include_str!("../../../assets/achievements/perfect_match.ron"),
+    ),
+    (
+        "perfect_match_total.ron",
+        include_str!("../../../assets/achievements/perfect_match_total.ron"),
+    ),
+    (
+        "plant_xxx.ron",
+        include_str!("../../../assets/achievements/plant_xxx.ron"),
+    ),
+    (
+        "score_xxx.ron",
+        include_str!("../../../assets/achievements/score_xxx.ron"),
+    ),
+    (
+        "time_xxx_s.ron",
+        include_str!("../../../assets/achievements/time_xxx_s.ron"),
+    ),
+    (
+        "zero_repaint.ron",
+        include_str!("../../../assets/achievements/zero_repaint.ron"),
+    ),
+    (
+        "zero_undo.ron",
+        include_str!("../../../assets/achievements/zero_undo.ron"),
+    ),
+];
+
+static REGISTRY: OnceLock<Vec<AchievementDef>> = OnceLock::new();
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum ConditionOp {
+    Eq,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Deserialize, Clone, Copy, Debug)]
+pub struct AchievementCondition {
+    pub field: StatusField,
+    pub op: ConditionOp,
+    #[serde(default)]
+    pub target: u32,
+}
+
+impl AchievementCondition {
+    pub fn check(&self, value: u32, target: u32) -> bool {
+        match self.op {
+            ConditionOp::Eq => value == target,
+            ConditionOp::Lt => value < target,
+            ConditionOp::Le => value <= target,
+            ConditionOp::Gt => value > target,
+            ConditionOp::Ge => value >= target,
+        }
+    }
+}
+
+// Round: the condition is checked against the status of the current round.
+// Total: every round meeting the condition (or every round without one) adds 1 to the progress.
+// Unlocked: the progress is the number of other unlocked achievements.
+#[derive(Deserialize, Clone, Copy, PartialEq, Default, Debug)]
+pub enum AchievementKind {
+    #[default]
+    Round,
+    Total,
+    Unlocked,
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Default, Debug)]
+pub enum ValueFormat {
+    #[default]
+    Plain,
+    Seconds,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct AchievementDef {
+    code: String,
+    name: String,
+    color: (f32, f32, f32),
+    #[serde(default)]
+    icon: Option<String>,
+    description: String,
+    progress_ui: AchievementProgressUi,
+    #[serde(default)]
+    kind: AchievementKind,
+    #[serde(default)]
+    condition: Option<AchievementCondition>,
+    #[serde(default)]
+    goal: u32,
+    #[serde(default)]
+    tiers: Option<[u32; 3]>,
+    #[serde(default)]
+    value_format: ValueFormat,
+}
+
+impl AchievementDef {
+    fn fetch_current(&self, status: &AppStatus, store: &AchievementStore) -> u32 {
+        match (self.kind, self.condition) {
+            (AchievementKind::Round, Some(condition)) => status.fetch(condition.field),
+            (AchievementKind::Round, None) => 0,
+            (AchievementKind::Total, _) => store.fetch_progress(&self.code),
+            (AchievementKind::Unlocked, _) => store.unlocked_count(&self.code),
+        }
+    }
+    fn check_value(&self, current: u32, target: u32) -> bool {
+        match (self.kind, self.condition) {
+            (AchievementKind::Round, Some(condition)) => condition.check(current, target),
+            (AchievementKind::Round, None) => false,
+            _ => current >= target,
+        }
+    }
+    fn fetch_goal(&self) -> u32 {
+        match (self.kind, self.condition) {
+            (AchievementKind::Round, Some(condition)) => condition.target,
+            _ => self.goal,
+        }
+    }
+}
+
+impl AchievementDefBase for AchievementDef {
+    fn code(&self) -> &str {
+        &self.code
+    }
+    fn name(&self) -> &str {
+        &self.name
+    }
+    fn color(&self) -> Color {
+        Color::rgb(self.color.0, self.color.1, self.color.2)
+    }
+    fn icon(&self) -> &str {
+        self.icon.as_deref().unwrap_or(&self.code)
+    }
+    fn description(&self) -> String {
+        self.description.clone()
+    }
+    fn check_done(&self, status: &ResMut<AppStatus>, store: &AchievementStore) -> (u32, u32, bool) {
+        let current = self.fetch_current(status, store);
+        let target = self
+            .tier_threshold(Some(AchievementTier::Gold))
+            .unwrap_or(self.fetch_goal());
+        (current, target, self.check_value(current, target))
+    }
+    fn progress_ui(&self) -> AchievementProgressUi {
+        self.progress_ui.clone()
+    }
+    fn format_value(&self, value: u32) -> String {
+        match self.value_format {
+            ValueFormat::Plain => format!("{}", value),
+            ValueFormat::Seconds if value % 100 == 0 => format!("{}", value / 100),
+            ValueFormat::Seconds => format!("{}.{:0>2}", value / 100, value % 100),
+        }
+    }
+    fn tiers(&self) -> Option<[u32; 3]> {
+        self.tiers
+    }
+    fn check_tier(
+        &self,
+        status: &ResMut<AppStatus>,
+        store: &AchievementStore,
+    ) -> Option<AchievementTier> {
+        let current = self.fetch_current(status, store);
+        ACHIEVEMENT_TIERS.into_iter().rev().find(|tier| {
+            self.tier_threshold(Some(*tier))
+                .is_some_and(|threshold| self.check_value(current, threshold))
+        })
+    }
+    fn count_round(&self, status: &ResMut<AppStatus>) -> bool {
+        self.kind == AchievementKind::Total
+            && self.condition.map_or(true, |condition| {
+                condition.check(status.fetch(condition.field), condition.target)
+            })
+    }
+    fn progress(&self, store: &AchievementStore) -> Option<(u32, u32)> {
+        let current = match self.kind {
+            AchievementKind::Round => return None,
+            AchievementKind::Total => store.fetch_progress(&self.code),
+            AchievementKind::Unlocked => store.unlocked_count(&self.code),
+        };
+        let target = match self.tiers {
+            Some(tiers) => tiers
+                .into_iter()
+                .find(|threshold| current < *threshold)
+                .unwrap_or(tiers[AchievementTier::Gold.index()]),
+            None => self.goal,
+        };
+        Some((current, target))
+    }
+}
+
+pub fn achievements() -> impl Iterator<Item = &'static dyn AchievementDefBase> {
+    REGISTRY
+        .get_or_init(load_defs)
+        .iter()
+        .map(|ach_def| ach_def as &dyn AchievementDefBase)
+}
+
+pub fn fetch_ach_def(code: &str) -> Option<&'static dyn AchievementDefBase> {
+    achievements().find(|ach_def| ach_def.code() == code)
+}
+
+fn parse_def(file_name: &str, content: &str) -> Result<AchievementDef, String> {
+    let ach_def: AchievementDef = if file_name.ends_with(".json") {
+        serde_json::from_str(content).map_err(|error| error.to_string())?
+    } else {
+        ron::Options::default()
+            .with_default_extension(ron::extensions::Extensions::IMPLICIT_SOME)
+            .from_str(content)
+            .map_err(|error| error.to_string())?
+    };
+    if ach_def.kind == AchievementKind::Round && ach_def.condition.is_none() {
+        return Err(String::from("missing condition"));
+    }
+    Ok(ach_def)
+}
+
+fn load_defs() -> Vec<AchievementDef> {
+    let sources = fetch_def_sources().unwrap_or_else(|| {
+        BUILTIN_DEFS
+            .iter()
+            .map(|(file_name, content)| (file_name.to_string(), content.to_string()))
+            .collect()
+    });
+    let mut defs: Vec<AchievementDef> = vec![];
+    for (file_name, content) in sources {
+        match parse_def(&file_name, &content) {
+            Ok(ach_def) if defs.iter().any(|def| def.code == ach_def.code) => {
+                warn!(
+                    "skip duplicated achievement {} in {}",
+                    ach_def.code, file_name
+                )
+            }
+            Ok(ach_def) => defs.push(ach_def),
+            Err(error) => warn!("skip invalid achievement {}: {}", file_name, error),
+        }
+    }
+    defs
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn fetch_def_sources() -> Option<Vec<(String, String)>> {
+    use std::fs;
+    let dir = bevy::asset::io::file::FileAssetReader::get_base_path()
+        .join("assets")
+        .join("achievements");
+    let mut paths: Vec<_> = fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == "ron" || extension == "json")
+        })
+        .collect();
+    paths.sort();
+    Some(
+        paths
+            .iter()
+            .filter_map(|path| {
+                let file_name = path.file_name()?.to_str()?.to_string();
+                Some((file_name, fs::read_to_string(path).ok()?))
+            })
+            .collect(),
+    )
+}
+
+#[cfg(target_arch = "wasm32")]
+fn fetch_def_sources() -> Option<Vec<(String, String)>> {
+    None
+}
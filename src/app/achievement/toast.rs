@@ -0,0 +1,157 @@
+use crate::app::{
+    achievement::{fetch_ach_def, AchievementInfo},
+    audio, settings,
+    theme::*,
+    ui,
+};
+use bevy::prelude::*;
+use bevy_persistent::prelude::*;
+use bevy_tweening::{lens::UiPositionLens, *};
+use std::time::Duration;
+
+const TOAST_DONE_EVENT: u64 = 51;
+const TOAST_SLIDE_MS: u64 = 400;
+const TOAST_STAY_MS: u64 = 2000;
+const TOAST_HIDDEN_TOP: f32 = -120.0; // px
+const TOAST_Z_INDEX: i32 = 10;
+
+#[derive(Component)]
+pub struct AchievementToast;
+
+pub fn handle_toast_queue(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut ach_info: ResMut<AchievementInfo>,
+    audio_se_asset: Res<audio::AudioSeAsset>,
+    settings: Res<Persistent<settings::Settings>>,
+    toast_query: Query<Entity, With<AchievementToast>>,
+) {
+    if !toast_query.is_empty() {
+        return;
+    }
+    while let Some((code, tier)) = ach_info.next_done() {
+        let Some(ach_def) = fetch_ach_def(&code) else {
+            continue;
+        };
+        audio::play_se(
+            audio::AudioSe::Tada,
+            &mut commands,
+            &audio_se_asset,
+            settings.as_ref(),
+        );
+        let position = |top: Val| UiRect {
+            left: Val::Px(0.0),
+            right: Val::Auto,
+            top,
+            bottom: Val::Auto,
+        };
+        let slide_in = Tween::new(
+            EaseFunction::CubicOut,
+            Duration::from_millis(TOAST_SLIDE_MS),
+            UiPositionLens {
+                start: position(Val::Px(TOAST_HIDDEN_TOP)),
+                end: position(ui::px_p(ui::PAGE_PADDING)),
+            },
+        );
+        let slide_out = Tween::new(
+            EaseFunction::CubicIn,
+            Duration::from_millis(TOAST_SLIDE_MS),
+            UiPositionLens {
+                start: position(ui::px_p(ui::PAGE_PADDING)),
+                end: position(Val::Px(TOAST_HIDDEN_TOP)),
+            },
+        )
+        .with_completed_event(TOAST_DONE_EVENT);
+        let sequence = slide_in
+            .then(Delay::new(Duration::from_millis(TOAST_STAY_MS)))
+            .then(slide_out);
+        commands
+            .spawn((
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        left: Val::Px(0.0),
+                        top: Val::Px(TOAST_HIDDEN_TOP),
+                        width: Val::Percent(100.0),
+                        justify_content: JustifyContent::Center,
+                        ..default()
+                    },
+                    z_index: ZIndex::Global(TOAST_Z_INDEX),
+                    ..default()
+                },
+                Animator::new(sequence),
+                AchievementToast,
+            ))
+            .with_children(|parent| {
+                parent
+                    .spawn(NodeBundle {
+                        style: Style {
+                            align_items: AlignItems::Center,
+                            column_gap: ui::px_p(3.0),
+                            padding: UiRect::all(ui::px_p(3.0)),
+                            border: UiRect::all(ui::px_p(0.5)),
+                            ..default()
+                        },
+                        background_color: BG_COLOR.into(),
+                        border_color: ach_def.color().into(),
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        parent.spawn(ImageBundle {
+                            style: Style {
+                                width: Val::Px(ui::ICON_SIZE * 1.8),
+                                height: Val::Px(ui::ICON_SIZE * 1.8),
+                                ..default()
+                            },
+                            image: UiImage::new(asset_server.load(ach_def.icon_path())),
+                            ..default()
+                        });
+                        parent
+                            .spawn(NodeBundle {
+                                style: Style {
+                                    flex_direction: FlexDirection::Column,
+                                    row_gap: ui::px_p(1.0),
+                                    ..default()
+                                },
+                                ..default()
+                            })
+                            .with_children(|parent| {
+                                let title = match tier {
+                                    Some(tier) => format!("{} achievement unlocked", tier.name()),
+                                    None => String::from("Achievement unlocked"),
+                                };
+                                parent.spawn(TextBundle::from_section(
+                                    title,
+                                    TextStyle {
+                                        font: asset_server.load(FONT),
+                                        font_size: ui::FONT_SIZE * 0.6,
+                                        color: MUTE_COLOR,
+                                    },
+                                ));
+                                parent.spawn(TextBundle::from_section(
+                                    ach_def.tier_name(tier),
+                                    TextStyle {
+                                        font: asset_server.load(FONT),
+                                        font_size: ui::FONT_SIZE,
+                                        color: ach_def.color(),
+                                    },
+                                ));
+                            });
+                    });
+            });
+        return;
+    }
+}
+
+pub fn handle_toast_done(
+    mut commands: Commands,
+    mut tween_completed_events: EventReader<TweenCompleted>,
+) {
+    for tween_event in tween_completed_events.read() {
+        if tween_event.user_data == TOAST_DONE_EVENT {
+            if let Some(entity_commands) = commands.get_entity(tween_event.entity) {
+                entity_commands.despawn_recursive();
+            }
+        }
+    }
+}
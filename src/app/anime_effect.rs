@@ -20,18 +20,21 @@ pub struct AnimeEffectParam {
     pub pos_2: Vec2,
     pub width_start: f32,
     pub width_end: f32,
+    pub polylines: Vec<Vec<Vec2>>,
 }
 
 #[derive(Clone, PartialEq)]
 pub enum AnimeEffectKind {
     LineQ,
     CircleQ,
+    Grow,
 }
 
 #[derive(Component)]
 pub struct AnimeEffect {
     pub kind: AnimeEffectKind,
     pub segments: Vec<[Vec2; 4]>,
+    pub polylines: Vec<Vec<Vec2>>,
     pub pos_1: Vec2,
     pub pos_2: Vec2,
     pub layer: u64,
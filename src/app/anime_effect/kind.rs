@@ -1,6 +1,7 @@
 use crate::app::anime_effect::*;
 
 pub mod circle_q;
+pub mod grow;
 pub mod line_q;
 
 const ANIME_EFFECT_CANVAS_Z_INDEX: f32 = 1.0;
@@ -14,6 +15,7 @@ pub fn fetch_builder(kind: AnimeEffectKind) -> &'static dyn AnimeEffectKindBase
     match kind {
         AnimeEffectKind::CircleQ => &kind::circle_q::AnimeEffectKindCircleQ,
         AnimeEffectKind::LineQ => &kind::line_q::AnimeEffectKindLineQ,
+        AnimeEffectKind::Grow => &kind::grow::AnimeEffectKindGrow,
     }
 }
 
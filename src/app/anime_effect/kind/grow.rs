@@ -0,0 +1,123 @@
+use super::*;
+use crate::app::stroke;
+
+pub struct AnimeEffectKindGrow;
+
+const GROW_DURATION_MS: u64 = 1500;
+const GROW_DONE_LAYER: u64 = u64::MAX;
+
+impl AnimeEffectKindBase for AnimeEffectKindGrow {
+    fn create(&self, commands: &mut Commands, param: AnimeEffectParam) -> Entity {
+        let root_entity = commands
+            .spawn((SpatialBundle {
+                transform: Transform::from_xyz(
+                    param.pos_1.x,
+                    param.pos_1.y,
+                    ANIME_EFFECT_CANVAS_Z_INDEX,
+                ),
+                ..default()
+            },))
+            .id();
+        let ae = AnimeEffect {
+            kind: param.kind,
+            segments: vec![],
+            polylines: param.polylines,
+            pos_1: param.pos_1,
+            pos_2: param.pos_2,
+            layer: 0,
+            delta: 0.0,
+            color: param.color,
+            width: param.width_start,
+            radius: param.width_start,
+            root_entity,
+        };
+        let tween = Tween::new(
+            EaseFunction::QuadraticInOut,
+            Duration::from_millis(GROW_DURATION_MS),
+            lens::AnimeEffectLens::new((param.width_start, param.width_end)),
+        );
+        commands
+            .entity(root_entity)
+            .insert((ae, Animator::new(tween)));
+        root_entity
+    }
+
+    fn draw(&self, commands: &mut Commands, ae: &mut AnimeEffect) {
+        if ae.layer == GROW_DONE_LAYER {
+            return;
+        }
+        let (grown, tip) = grow_polylines(&ae.polylines, ae.delta);
+        if let Some(mut entity_commands) = commands.get_entity(ae.root_entity) {
+            entity_commands.despawn_descendants();
+            entity_commands.with_children(|parent| {
+                parent.spawn((
+                    ShapeBundle {
+                        path: stroke::build_polyline_path(&grown),
+                        ..default()
+                    },
+                    stroke::build_line_stroke(ae.color, ae.width),
+                ));
+                if let Some(tip) = tip {
+                    let circle = shapes::Circle {
+                        radius: ae.width,
+                        center: tip,
+                    };
+                    parent.spawn((
+                        ShapeBundle {
+                            path: GeometryBuilder::build_as(&circle),
+                            spatial: SpatialBundle {
+                                transform: Transform::from_xyz(0.0, 0.0, 0.001),
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        Fill::color(ae.color),
+                    ));
+                }
+            });
+        }
+        ae.layer = if ae.delta >= 1.0 {
+            GROW_DONE_LAYER
+        } else {
+            ae.layer + 1
+        };
+    }
+}
+
+fn grow_polylines(polylines: &[Vec<Vec2>], delta: f32) -> (Vec<Vec<Vec2>>, Option<Vec2>) {
+    let total_length: f32 = polylines
+        .iter()
+        .flat_map(|polyline| polyline.windows(2))
+        .map(|pair| pair[0].distance(pair[1]))
+        .sum();
+    if delta >= 1.0 {
+        return (polylines.to_vec(), None);
+    }
+    let mut remain = total_length * delta;
+    let mut grown: Vec<Vec<Vec2>> = vec![];
+    let mut tip: Option<Vec2> = None;
+    for polyline in polylines.iter() {
+        let first = match polyline.first() {
+            Some(pos) => *pos,
+            None => continue,
+        };
+        if remain <= 0.0 {
+            break;
+        }
+        let mut grown_polyline = vec![first];
+        for pair in polyline.windows(2) {
+            let length = pair[0].distance(pair[1]);
+            if remain < length {
+                let pos = pair[0].lerp(pair[1], remain / length);
+                grown_polyline.push(pos);
+                tip = Some(pos);
+                remain = 0.0;
+                break;
+            }
+            grown_polyline.push(pair[1]);
+            remain -= length;
+        }
+        grown.push(grown_polyline);
+    }
+    (grown, tip)
+}
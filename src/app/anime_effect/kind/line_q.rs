@@ -17,6 +17,7 @@ impl AnimeEffectKindBase for AnimeEffectKindLineQ {
         let ae = AnimeEffect {
             kind: param.kind,
             segments,
+            polylines: param.polylines,
             pos_1: param.pos_1,
             pos_2: param.pos_2,
             layer: 0,
@@ -0,0 +1,246 @@
+use bevy::prelude::*;
+use bevy_persistent::{error::PersistenceError, prelude::*, storage::Storage};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    collections::VecDeque,
+    fs,
+    marker::PhantomData,
+    path::{Path, PathBuf},
+};
+
+pub const BACKUP_COUNT: usize = 3;
+const BACKUP_DIR: &str = "backups";
+
+#[derive(Resource, Default)]
+pub struct BackupNotice {
+    pub messages: Vec<String>,
+}
+
+pub struct Loaded<R: Resource + Serialize + DeserializeOwned> {
+    pub persistent: Persistent<R>,
+    pub notice: Option<String>,
+    pub is_migrated: bool,
+}
+
+// NOTE: inserted next to the resource when it was migrated from the baseline format on load
+#[derive(Resource)]
+pub struct Migrated<R: Resource>(PhantomData<R>);
+
+pub fn insert_loaded<R: Resource + Serialize + DeserializeOwned>(app: &mut App, loaded: Loaded<R>) {
+    app.insert_resource(loaded.persistent);
+    if loaded.is_migrated {
+        app.insert_resource(Migrated::<R>(PhantomData));
+    }
+    push_notice(app, loaded.notice);
+}
+
+pub fn push_notice(app: &mut App, message: Option<String>) {
+    if let Some(message) = message {
+        app.world
+            .get_resource_or_insert_with(BackupNotice::default)
+            .messages
+            .push(message);
+    }
+}
+
+// NOTE: keeps the backed up data in memory so it is not read from disk on every change
+#[derive(Resource)]
+pub struct BackupCache<R: Resource + Clone> {
+    backups: VecDeque<R>,
+}
+
+impl<R: Resource + Serialize + DeserializeOwned + Clone> BackupCache<R> {
+    pub fn load(
+        path: &Path,
+        build: impl Fn(&Path) -> Result<Persistent<R>, PersistenceError>,
+    ) -> Self {
+        Self {
+            backups: (1..=BACKUP_COUNT)
+                .map(|index| fetch_backup_path(path, index))
+                .filter(|backup_path| backup_path.exists())
+                .filter_map(|backup_path| build(&backup_path).ok())
+                .map(|backup| backup.get().clone())
+                .collect(),
+        }
+    }
+    pub fn rotate(&mut self, persistent: &Persistent<R>) {
+        if rotate_persisted(persistent) {
+            self.backups.push_front(persistent.get().clone());
+            self.backups.truncate(BACKUP_COUNT);
+        }
+    }
+    pub fn backups(&self) -> impl Iterator<Item = &R> {
+        self.backups.iter()
+    }
+}
+
+pub fn rotate_persisted_backups<R: Resource + Serialize + DeserializeOwned>(
+    persistent: Res<Persistent<R>>,
+) {
+    if persistent.is_changed() {
+        rotate_persisted(&persistent);
+    }
+}
+
+pub fn rotate_cached_backups<R: Resource + Serialize + DeserializeOwned + Clone>(
+    persistent: Res<Persistent<R>>,
+    mut cache: ResMut<BackupCache<R>>,
+) {
+    if persistent.is_changed() {
+        cache.rotate(&persistent);
+    }
+}
+
+pub fn rotate_persisted<R: Resource + Serialize + DeserializeOwned>(
+    persistent: &Persistent<R>,
+) -> bool {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let Storage::Filesystem { path } = persistent.storage();
+        rotate_backups(path)
+    }
+    #[cfg(target_arch = "wasm32")]
+    {
+        let _ = persistent;
+        false
+    }
+}
+
+pub fn load_with_backups<R: Resource + Serialize + DeserializeOwned>(
+    name: &str,
+    path: &Path,
+    build: impl Fn(&Path) -> Result<Persistent<R>, PersistenceError>,
+    migrate: impl Fn(&Path) -> Option<Persistent<R>>,
+) -> Result<Loaded<R>, String> {
+    let loaded = match build(path) {
+        Ok(persistent) => Some((persistent, false)),
+        Err(_) => migrate(path).map(|persistent| (persistent, true)),
+    };
+    if let Some((persistent, is_migrated)) = loaded {
+        rotate_backups(path);
+        return Ok(Loaded {
+            persistent,
+            notice: None,
+            is_migrated,
+        });
+    }
+    let corrupt_path = fetch_corrupt_path(path);
+    fs::copy(path, &corrupt_path).ok();
+    for index in 1..=BACKUP_COUNT {
+        let backup_path = fetch_backup_path(path, index);
+        if !backup_path.exists() || build(&backup_path).is_err() {
+            continue;
+        }
+        if fs::copy(&backup_path, path).is_err() {
+            continue;
+        }
+        if let Ok(persistent) = build(path) {
+            return Ok(Loaded {
+                persistent,
+                notice: Some(format!(
+                    "Failed to load the {}, restored from backup {} (broken file kept as {})",
+                    name,
+                    index,
+                    corrupt_path.display()
+                )),
+                is_migrated: false,
+            });
+        }
+    }
+    Err(format!(
+        "Failed to load the {} and no valid backup was found (broken file kept as {})",
+        name,
+        corrupt_path.display()
+    ))
+}
+
+pub fn fetch_backup_path(path: &Path, index: usize) -> PathBuf {
+    let file_name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default();
+    path.parent()
+        .unwrap_or(Path::new("."))
+        .join(BACKUP_DIR)
+        .join(format!("{}.{}", file_name, index))
+}
+
+fn fetch_corrupt_path(path: &Path) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(".corrupt");
+    path.with_file_name(file_name)
+}
+
+fn rotate_backups(path: &Path) -> bool {
+    let data = match fs::read(path) {
+        Ok(data) => data,
+        Err(_) => return false,
+    };
+    let latest_path = fetch_backup_path(path, 1);
+    if fs::read(&latest_path).is_ok_and(|latest| latest == data) {
+        return false;
+    }
+    if let Some(dir) = latest_path.parent() {
+        if fs::create_dir_all(dir).is_err() {
+            return false;
+        }
+    }
+    for index in (1..BACKUP_COUNT).rev() {
+        let from = fetch_backup_path(path, index);
+        if from.exists() {
+            fs::rename(&from, fetch_backup_path(path, index + 1)).ok();
+        }
+    }
+    fs::write(latest_path, data).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Resource, Serialize, Deserialize, Clone, PartialEq, Debug)]
+    struct TestData {
+        value: u32,
+    }
+
+    fn build_data(path: &Path) -> Result<Persistent<TestData>, PersistenceError> {
+        Persistent::<TestData>::builder()
+            .name("test data")
+            .format(StorageFormat::Json)
+            .path(path)
+            .default(TestData { value: 0 })
+            .build()
+    }
+
+    #[test]
+    fn persisted_data_is_restored_from_backup() {
+        let dir = std::env::temp_dir().join(format!("gaia_s_pen_backup_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let path = dir.join("data.json");
+        let mut data = build_data(&path).unwrap();
+        let mut cache = BackupCache::load(&path, build_data);
+        for value in 1..=BACKUP_COUNT as u32 + 1 {
+            data.set(TestData { value }).unwrap();
+            cache.rotate(&data);
+        }
+        for index in 1..=BACKUP_COUNT {
+            let backup = build_data(&fetch_backup_path(&path, index)).unwrap();
+            assert_eq!(backup.value, BACKUP_COUNT as u32 + 2 - index as u32);
+        }
+        let cached_values: Vec<u32> = cache.backups().map(|backup| backup.value).collect();
+        let loaded_values: Vec<u32> = BackupCache::load(&path, build_data)
+            .backups()
+            .map(|backup| backup.value)
+            .collect();
+        assert_eq!(cached_values, loaded_values);
+
+        fs::write(&path, "broken").unwrap();
+        let restored = load_with_backups("test data", &path, build_data, |_| None).unwrap();
+        assert_eq!(restored.persistent.value, BACKUP_COUNT as u32 + 1);
+        assert!(restored.notice.is_some());
+        assert!(!restored.is_migrated);
+        assert!(fetch_corrupt_path(&path).exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
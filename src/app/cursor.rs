@@ -6,6 +6,23 @@ pub struct AppCursorData {
     pub canvas_pos: Vec2,
 }
 
+impl AppCursorData {
+    pub fn set_window_pos(&mut self, window_pos: Vec2, window: &Window) {
+        let win_w = window.resolution.width();
+        let win_h = window.resolution.height();
+        self.window_pos = window_pos;
+        self.canvas_pos =
+            Vec2::new(window_pos.x, -window_pos.y) - Vec2::new(win_w / 2.0, -win_h / 2.0);
+    }
+
+    pub fn set_canvas_pos(&mut self, canvas_pos: Vec2, window: &Window) {
+        let win_w = window.resolution.width();
+        let win_h = window.resolution.height();
+        self.canvas_pos = canvas_pos;
+        self.window_pos = Vec2::new(canvas_pos.x + win_w / 2.0, win_h / 2.0 - canvas_pos.y);
+    }
+}
+
 pub struct AppCursorPlugin;
 
 impl Plugin for AppCursorPlugin {
@@ -21,11 +38,6 @@ fn update_cursor_pos(
     window: Query<&Window>,
 ) {
     for cursor_event in cursor_moved_events.read() {
-        cursor_data.window_pos = cursor_event.position;
-        let window = window.single();
-        let win_w = window.resolution.width();
-        let win_h = window.resolution.height();
-        cursor_data.canvas_pos = Vec2::new(cursor_event.position.x, -cursor_event.position.y)
-            - Vec2::new(win_w / 2.0, -win_h / 2.0);
+        cursor_data.set_window_pos(cursor_event.position, window.single());
     }
 }
@@ -0,0 +1,443 @@
+use crate::app::{
+    self,
+    achievement::{self, AchievementRecord, AchievementStore},
+    integrity::{self, IntegrityReport},
+    leaderboard::{self, Leaderboard, LeaderboardRecord},
+    screenshot,
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use bevy_persistent::prelude::*;
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+pub const BUNDLE_VERSION: u32 = 1;
+const BUNDLE_PREFIX: &str = "bundle_";
+
+#[derive(Serialize, Deserialize)]
+pub struct DataBundle {
+    pub version: u32,
+    pub exported_at: String,
+    pub leaderboard: Leaderboard,
+    pub achievement_store: AchievementStore,
+    pub screenshots: Vec<BundleScreenshot>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct BundleScreenshot {
+    pub uid: String,
+    pub screenshot_type: String,
+    pub data: String,
+}
+
+pub struct ImportResult {
+    pub record_count: usize,
+    pub achievement_count: usize,
+    pub flagged_count: usize,
+}
+
+pub fn fetch_export_dir_path() -> PathBuf {
+    dirs::download_dir()
+        .or_else(dirs::home_dir)
+        .unwrap_or(PathBuf::from("."))
+        .join(app::APP_CODE)
+}
+
+pub fn export_record(record: &LeaderboardRecord, screenshot_dir: &Path) -> Result<PathBuf, String> {
+    let export_dir = fetch_export_dir_path().join(format!("record_{}", to_file_name(record.uid())));
+    fs::create_dir_all(&export_dir).map_err(|error| error.to_string())?;
+    let json = serde_json::to_string_pretty(record).map_err(|error| error.to_string())?;
+    fs::write(export_dir.join("record.json"), json).map_err(|error| error.to_string())?;
+    for (screenshot_type, path) in
+        screenshot::fetch_leaderboard_screenshot_paths(screenshot_dir, record.uid())
+    {
+        fs::copy(path, export_dir.join(format!("{}.png", screenshot_type)))
+            .map_err(|error| error.to_string())?;
+    }
+    Ok(export_dir)
+}
+
+pub fn build_bundle(
+    leaderboard: &Leaderboard,
+    achievement_store: &AchievementStore,
+    screenshot_dir: &Path,
+) -> DataBundle {
+    let mut screenshots = vec![];
+    for record in leaderboard.records() {
+        for (screenshot_type, path) in
+            screenshot::fetch_leaderboard_screenshot_paths(screenshot_dir, record.uid())
+        {
+            if let Ok(data) = fs::read(path) {
+                screenshots.push(BundleScreenshot {
+                    uid: record.uid().to_string(),
+                    screenshot_type: screenshot_type.to_string(),
+                    data: STANDARD.encode(data),
+                });
+            }
+        }
+    }
+    DataBundle {
+        version: BUNDLE_VERSION,
+        exported_at: Local::now().format("%Y-%m-%d_%H:%M:%S%.9f").to_string(),
+        leaderboard: leaderboard.clone(),
+        achievement_store: achievement_store.clone(),
+        screenshots,
+    }
+}
+
+pub fn export_bundle(
+    leaderboard: &Leaderboard,
+    achievement_store: &AchievementStore,
+    screenshot_dir: &Path,
+    path: Option<&Path>,
+) -> Result<PathBuf, String> {
+    let bundle = build_bundle(leaderboard, achievement_store, screenshot_dir);
+    let path = match path {
+        Some(path) => path.to_path_buf(),
+        None => fetch_export_dir_path().join(format!(
+            "{}{}.json",
+            BUNDLE_PREFIX,
+            to_file_name(&bundle.exported_at)
+        )),
+    };
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|error| error.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(&bundle).map_err(|error| error.to_string())?;
+    fs::write(&path, json).map_err(|error| error.to_string())?;
+    Ok(path)
+}
+
+pub fn read_bundle(path: &Path) -> Result<DataBundle, String> {
+    let json = fs::read_to_string(path).map_err(|error| error.to_string())?;
+    let bundle: DataBundle = serde_json::from_str(&json).map_err(|error| error.to_string())?;
+    if !(1..=BUNDLE_VERSION).contains(&bundle.version) {
+        return Err(format!("unsupported bundle version {}", bundle.version));
+    }
+    Ok(bundle)
+}
+
+pub fn import_bundle(
+    bundle: &DataBundle,
+    leaderboard: &mut Persistent<Leaderboard>,
+    achievement_store: &mut Persistent<AchievementStore>,
+    report: &mut IntegrityReport,
+    screenshot_dir: &Path,
+) -> Result<ImportResult, String> {
+    let new_uids: Vec<&str> = bundle
+        .leaderboard
+        .records()
+        .iter()
+        .map(|record| record.uid())
+        .filter(|uid| leaderboard.fetch_record(uid).is_none())
+        .collect();
+    let old_achievements: Vec<AchievementRecord> = achievement_store.records().cloned().collect();
+    let old_progresses: Vec<(String, u32)> = achievement_store
+        .progresses()
+        .map(|(code, value)| (code.clone(), *value))
+        .collect();
+    for bundle_screenshot in bundle.screenshots.iter() {
+        if !leaderboard::is_valid_uid(&bundle_screenshot.uid)
+            || !screenshot::is_screenshot_type(&bundle_screenshot.screenshot_type)
+        {
+            continue;
+        }
+        let is_new_record = leaderboard
+            .fetch_record(bundle_screenshot.uid.as_str())
+            .is_none();
+        if let (true, Ok(data)) = (is_new_record, STANDARD.decode(&bundle_screenshot.data)) {
+            screenshot::import_leaderboard_screenshot(
+                screenshot_dir,
+                bundle_screenshot.uid.as_str(),
+                bundle_screenshot.screenshot_type.as_str(),
+                &data,
+            );
+        }
+    }
+    let record_count = leaderboard
+        .merge_with_screenshot_dir(bundle.leaderboard.records().to_vec(), screenshot_dir);
+    leaderboard.persist().map_err(|error| error.to_string())?;
+    let achievement_count = achievement_store.merge(&bundle.achievement_store);
+    achievement_store
+        .persist()
+        .map_err(|error| error.to_string())?;
+    // NOTE: anyone can edit and re-sign a bundle, so imported data is never trusted
+    let mut flagged_count = 0;
+    for record in bundle.leaderboard.records() {
+        let is_imported =
+            new_uids.contains(&record.uid()) && leaderboard.fetch_record(record.uid()).is_some();
+        if is_imported && report.flagged_records.insert(record.uid().to_string()) {
+            flagged_count += 1;
+        }
+    }
+    for record in bundle.achievement_store.records() {
+        let is_stored = |stored: &AchievementRecord| {
+            stored.code == record.code
+                && (stored.is_done || stored.tier.is_some())
+                && stored.done_at == record.done_at
+        };
+        let is_imported =
+            achievement_store.records().any(is_stored) && !old_achievements.iter().any(is_stored);
+        if is_imported && report.flagged_achievements.insert(record.code.clone()) {
+            flagged_count += 1;
+        }
+    }
+    for (code, value) in bundle.achievement_store.progresses() {
+        let old_value = old_progresses
+            .iter()
+            .find(|(old_code, _)| old_code == code)
+            .map_or(0, |(_, old_value)| *old_value);
+        let is_imported = *value > old_value && achievement_store.fetch_progress(code) == *value;
+        if is_imported && report.flagged_achievements.insert(code.clone()) {
+            flagged_count += 1;
+        }
+    }
+    Ok(ImportResult {
+        record_count,
+        achievement_count,
+        flagged_count,
+    })
+}
+
+pub fn fetch_bundle_paths() -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(fetch_export_dir_path()) else {
+        return vec![];
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(BUNDLE_PREFIX) && name.ends_with(".json"))
+        })
+        .collect();
+    paths.sort_by(|a, b| b.cmp(a));
+    paths
+}
+
+pub fn fetch_latest_bundle_path() -> Option<PathBuf> {
+    fetch_bundle_paths().into_iter().next()
+}
+
+pub enum CliAction {
+    Export(Option<PathBuf>),
+    Import(Option<PathBuf>),
+}
+
+pub fn parse_cli_args(args: impl Iterator<Item = String>) -> Option<CliAction> {
+    let mut args = args.skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--export" => return Some(CliAction::Export(args.next().map(PathBuf::from))),
+            "--import" => return Some(CliAction::Import(args.next().map(PathBuf::from))),
+            _ => (),
+        }
+    }
+    None
+}
+
+pub fn run_cli_action(action: CliAction) -> Result<String, String> {
+    let loaded_leaderboard = leaderboard::load_leaderboard();
+    let loaded_achievement_store = achievement::load_achievement_store();
+    for notice in [&loaded_leaderboard.notice, &loaded_achievement_store.notice]
+        .into_iter()
+        .flatten()
+    {
+        eprintln!("{}", notice);
+    }
+    let (mut integrity, mut report) = integrity::load_integrity(
+        &loaded_leaderboard.persistent,
+        &loaded_achievement_store.persistent,
+        loaded_leaderboard.is_migrated,
+        loaded_achievement_store.is_migrated,
+    );
+    let mut leaderboard = loaded_leaderboard.persistent;
+    let mut achievement_store = loaded_achievement_store.persistent;
+    let screenshot_dir = screenshot::fetch_screenshots_dir_path();
+    match action {
+        CliAction::Export(path) => {
+            let path = export_bundle(
+                &leaderboard,
+                &achievement_store,
+                &screenshot_dir,
+                path.as_deref(),
+            )?;
+            Ok(format!("Exported to {}", path.display()))
+        }
+        CliAction::Import(path) => {
+            let path = path
+                .or_else(fetch_latest_bundle_path)
+                .ok_or("no bundle to import")?;
+            let bundle = read_bundle(&path)?;
+            let result = import_bundle(
+                &bundle,
+                &mut leaderboard,
+                &mut achievement_store,
+                &mut report,
+                &screenshot_dir,
+            )?;
+            let mut backup_cache = leaderboard::load_backup_cache();
+            backup_cache.rotate(&leaderboard);
+            app::backup::rotate_persisted(&achievement_store);
+            let backed_up_uids = leaderboard::fetch_backed_up_uids(&backup_cache);
+            integrity
+                .update(|integrity| {
+                    integrity.sign_leaderboard(
+                        &leaderboard,
+                        &report.flagged_records,
+                        &backed_up_uids,
+                    );
+                    integrity
+                        .sign_achievement_store(&achievement_store, &report.flagged_achievements);
+                })
+                .map_err(|error| error.to_string())?;
+            Ok(format!(
+                "Imported {} records and {} achievements from {}{}",
+                result.record_count,
+                result.achievement_count,
+                path.display(),
+                format_flagged_count(result.flagged_count)
+            ))
+        }
+    }
+}
+
+pub fn format_flagged_count(flagged_count: usize) -> String {
+    if flagged_count > 0 {
+        format!(", {} unverified", flagged_count)
+    } else {
+        String::new()
+    }
+}
+
+fn to_file_name(uid: &str) -> String {
+    uid.replace([':', '.'], "-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::integrity::IntegrityStore;
+    use bevy::utils::HashSet;
+
+    fn build_persistent<R: bevy::prelude::Resource + Serialize + serde::de::DeserializeOwned>(
+        path: &Path,
+        default: R,
+    ) -> Persistent<R> {
+        let _ = fs::remove_file(path);
+        Persistent::<R>::builder()
+            .name("import test")
+            .format(StorageFormat::Bincode)
+            .path(path)
+            .default(default)
+            .build()
+            .unwrap()
+    }
+
+    fn build_exported_data() -> (Leaderboard, AchievementStore) {
+        let leaderboard = Leaderboard::from_records(
+            (0..3)
+                .map(|i| LeaderboardRecord {
+                    score: 100 + i,
+                    started_at: format!("1999-02-01_00:00:{:02}.000000000", i),
+                    ..Default::default()
+                })
+                .collect(),
+        );
+        let mut store = AchievementStore::default();
+        store.mark_done("zero_undo");
+        store.add_progress("perfect_match_total", 3);
+        (leaderboard, store)
+    }
+
+    fn build_screenshot_dir(name: &str) -> PathBuf {
+        let screenshot_dir = std::env::temp_dir().join(format!(
+            "gaia_s_pen_export_{}_screenshots_{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&screenshot_dir);
+        screenshot_dir
+    }
+
+    fn import(
+        bundle: &DataBundle,
+        name: &str,
+        report: &mut IntegrityReport,
+        screenshot_dir: &Path,
+    ) -> ImportResult {
+        let dir = std::env::temp_dir();
+        let leaderboard_path = dir.join(format!(
+            "gaia_s_pen_import_{}_leaderboard_{}.bin",
+            name,
+            std::process::id()
+        ));
+        let store_path = dir.join(format!(
+            "gaia_s_pen_import_{}_achievement_{}.bin",
+            name,
+            std::process::id()
+        ));
+        let mut leaderboard =
+            build_persistent(&leaderboard_path, Leaderboard::from_records(vec![]));
+        let mut store = build_persistent(&store_path, AchievementStore::default());
+        let result =
+            import_bundle(bundle, &mut leaderboard, &mut store, report, screenshot_dir).unwrap();
+        let _ = fs::remove_file(leaderboard_path);
+        let _ = fs::remove_file(store_path);
+        result
+    }
+
+    #[test]
+    fn resigned_bundle_is_flagged() {
+        let (leaderboard, store) = build_exported_data();
+        let mut records = leaderboard.records().to_vec();
+        records[0].score += 1000;
+        let screenshot_dir = build_screenshot_dir("resigned");
+        let mut bundle = build_bundle(&leaderboard, &store, &screenshot_dir);
+        bundle.leaderboard = Leaderboard::from_records(records);
+        bundle
+            .achievement_store
+            .add_progress("perfect_match_total", 1);
+        let mut integrity = IntegrityStore::default().with_key("editor key");
+        integrity.sign_leaderboard(&bundle.leaderboard, &HashSet::new(), &HashSet::new());
+        integrity.sign_achievement_store(&bundle.achievement_store, &HashSet::new());
+        let mut json = serde_json::to_value(&bundle).unwrap();
+        json["integrity"] = serde_json::to_value(&integrity).unwrap();
+        let resigned: DataBundle = serde_json::from_value(json).unwrap();
+        let mut report = IntegrityReport::default();
+        let result = import(&resigned, "resigned", &mut report, &screenshot_dir);
+        assert_eq!(result.record_count, 3);
+        assert_eq!(result.achievement_count, 1);
+        assert_eq!(result.flagged_count, 5);
+        assert!(report.is_record_flagged(leaderboard.records()[0].uid()));
+        assert!(report.is_achievement_flagged("zero_undo"));
+        assert!(report.is_achievement_flagged("perfect_match_total"));
+        let _ = fs::remove_dir_all(&screenshot_dir);
+    }
+
+    #[test]
+    fn screenshots_are_carried_by_the_bundle() {
+        let (leaderboard, store) = build_exported_data();
+        let uid = leaderboard.records()[0].uid();
+        let export_dir = build_screenshot_dir("source");
+        screenshot::import_leaderboard_screenshot(&export_dir, uid, "score", b"score");
+        let bundle = build_bundle(&leaderboard, &store, &export_dir);
+        assert_eq!(bundle.screenshots.len(), 1);
+
+        let import_dir = build_screenshot_dir("target");
+        import(
+            &bundle,
+            "screenshots",
+            &mut IntegrityReport::default(),
+            &import_dir,
+        );
+        let paths = screenshot::fetch_leaderboard_screenshot_paths(&import_dir, uid);
+        assert_eq!(paths.len(), 1);
+        assert_eq!(fs::read(&paths[0].1).unwrap(), b"score");
+        let _ = fs::remove_dir_all(&export_dir);
+        let _ = fs::remove_dir_all(&import_dir);
+    }
+}
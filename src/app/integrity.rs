@@ -0,0 +1,380 @@
+use crate::app::{
+    self,
+    achievement::{AchievementRecord, AchievementStore},
+    backup::{BackupCache, Migrated},
+    leaderboard::{self, Leaderboard, LeaderboardRecord},
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use bevy::{prelude::*, utils::HashMap, utils::HashSet};
+use bevy_persistent::prelude::*;
+use chrono::Local;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::{path::Path, sync::OnceLock};
+
+const INSTALL_KEY_SIZE: usize = 32;
+
+static INSTALL_KEY: OnceLock<String> = OnceLock::new();
+
+#[derive(Resource, Serialize, Deserialize)]
+struct InstallKey {
+    key: String,
+    created_at: String,
+}
+
+#[derive(Resource, Serialize, Deserialize, Clone, Default)]
+pub struct IntegrityStore {
+    #[serde(skip)]
+    key: Option<String>,
+    leaderboard: HashMap<String, String>,
+    achievement: HashMap<String, String>,
+    #[serde(default)]
+    progress: HashMap<String, String>,
+}
+
+impl IntegrityStore {
+    pub fn with_key(mut self, key: &str) -> Self {
+        self.key = Some(String::from(key));
+        self
+    }
+    pub fn verify_record(&self, record: &LeaderboardRecord) -> bool {
+        self.leaderboard.get(record.uid()) == Some(&self.sign_record(record))
+    }
+    pub fn verify_achievement(&self, record: &AchievementRecord) -> bool {
+        !is_reached(record)
+            || self.achievement.get(&record.code) == Some(&self.sign_achievement(record))
+    }
+    pub fn verify_progress(&self, code: &str, value: u32) -> bool {
+        value == 0 || self.progress.get(code) == Some(&self.sign_progress(code, value))
+    }
+    pub fn sign_leaderboard(
+        &mut self,
+        leaderboard: &Leaderboard,
+        flagged: &HashSet<String>,
+        backed_up_uids: &HashSet<String>,
+    ) {
+        let records = leaderboard.records();
+        let signatures: Vec<(String, String)> = records
+            .iter()
+            .filter(|record| !flagged.contains(record.uid()))
+            .map(|record| (record.uid().to_string(), self.sign_record(record)))
+            .collect();
+        // NOTE: keep the signatures of backed up records, they come back on a restore
+        self.leaderboard.retain(|uid, _| {
+            backed_up_uids.contains(uid) || records.iter().any(|record| record.uid() == uid)
+        });
+        self.leaderboard.extend(signatures);
+    }
+    pub fn sign_achievement_store(&mut self, store: &AchievementStore, flagged: &HashSet<String>) {
+        let signatures: Vec<(String, String)> = store
+            .records()
+            .filter(|record| is_reached(record) && !flagged.contains(&record.code))
+            .map(|record| (record.code.clone(), self.sign_achievement(record)))
+            .collect();
+        let progress_signatures: Vec<(String, String)> = store
+            .progresses()
+            .filter(|(code, value)| **value > 0 && !flagged.contains(*code))
+            .map(|(code, value)| (code.clone(), self.sign_progress(code, *value)))
+            .collect();
+        self.achievement.retain(|code, _| {
+            store
+                .records()
+                .any(|record| &record.code == code && is_reached(record))
+        });
+        self.achievement.extend(signatures);
+        self.progress
+            .retain(|code, _| store.fetch_progress(code) > 0);
+        self.progress.extend(progress_signatures);
+    }
+    fn key(&self) -> &str {
+        self.key.as_deref().unwrap_or_else(|| install_key())
+    }
+    fn sign(&self, data: &[u8]) -> String {
+        let mut mac =
+            Hmac::<Sha256>::new_from_slice(self.key().as_bytes()).expect("invalid integrity key");
+        mac.update(data);
+        STANDARD.encode(mac.finalize().into_bytes())
+    }
+    fn sign_record(&self, record: &LeaderboardRecord) -> String {
+        self.sign(&serde_json::to_vec(record).expect("failed to serialize leaderboard record"))
+    }
+    fn sign_achievement(&self, record: &AchievementRecord) -> String {
+        self.sign(&serde_json::to_vec(record).expect("failed to serialize achievement record"))
+    }
+    fn sign_progress(&self, code: &str, value: u32) -> String {
+        let data = serde_json::to_vec(&ProgressEntry { code, value });
+        self.sign(&data.expect("failed to serialize achievement progress"))
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct IntegrityReport {
+    pub flagged_records: HashSet<String>,
+    pub flagged_achievements: HashSet<String>,
+}
+
+impl IntegrityReport {
+    pub fn new(
+        integrity: &IntegrityStore,
+        leaderboard: &Leaderboard,
+        store: &AchievementStore,
+    ) -> Self {
+        Self {
+            flagged_records: leaderboard
+                .records()
+                .iter()
+                .filter(|record| !integrity.verify_record(record))
+                .map(|record| record.uid().to_string())
+                .collect(),
+            flagged_achievements: store
+                .records()
+                .filter(|record| !integrity.verify_achievement(record))
+                .map(|record| record.code.clone())
+                .chain(
+                    store
+                        .progresses()
+                        .filter(|(code, value)| !integrity.verify_progress(code, **value))
+                        .map(|(code, _)| code.clone()),
+                )
+                .collect(),
+        }
+    }
+    pub fn is_record_flagged(&self, uid: &str) -> bool {
+        self.flagged_records.contains(uid)
+    }
+    pub fn is_achievement_flagged(&self, code: &str) -> bool {
+        self.flagged_achievements.contains(code)
+    }
+    pub fn is_empty(&self) -> bool {
+        self.flagged_records.is_empty() && self.flagged_achievements.is_empty()
+    }
+}
+
+fn is_reached(record: &AchievementRecord) -> bool {
+    record.is_done || record.tier.is_some()
+}
+
+fn install_key() -> &'static str {
+    INSTALL_KEY.get_or_init(load_install_key)
+}
+
+fn generate_key() -> String {
+    STANDARD.encode(rand::random::<[u8; INSTALL_KEY_SIZE]>())
+}
+
+fn load_install_key() -> String {
+    let data_dir = if cfg!(test) {
+        std::env::temp_dir().join(app::APP_CODE)
+    } else {
+        dirs::data_local_dir()
+            .map(|native_data_dir| native_data_dir.join(app::APP_CODE))
+            .unwrap_or(Path::new("local").join("data"))
+    };
+    let path = data_dir.join("integrity_key.json");
+    let key = Persistent::<InstallKey>::builder()
+        .name("integrity key")
+        .format(StorageFormat::Json)
+        .path(path)
+        .default(InstallKey {
+            key: generate_key(),
+            created_at: Local::now().format("%Y-%m-%d_%H:%M:%S%.9f").to_string(),
+        })
+        .revertible(true)
+        .revert_to_default_on_deserialization_errors(true)
+        .build()
+        .expect("failed to initialize variables");
+    key.key.clone()
+}
+
+#[derive(Serialize)]
+struct ProgressEntry<'a> {
+    code: &'a str,
+    value: u32,
+}
+
+pub fn load_integrity(
+    leaderboard: &Leaderboard,
+    store: &AchievementStore,
+    is_leaderboard_migrated: bool,
+    is_ach_store_migrated: bool,
+) -> (Persistent<IntegrityStore>, IntegrityReport) {
+    let config_dir = dirs::config_dir()
+        .map(|native_config_dir| native_config_dir.join(app::APP_CODE))
+        .unwrap_or(Path::new("local").join("configuration"));
+    let path = config_dir.join("integrity.json");
+    let mut integrity = Persistent::<IntegrityStore>::builder()
+        .name("integrity")
+        .format(StorageFormat::Json)
+        .path(path)
+        .default(IntegrityStore::default())
+        .revertible(true)
+        .revert_to_default_on_deserialization_errors(true)
+        .build()
+        .expect("failed to initialize variables");
+    // NOTE: only data migrated from the unsigned baseline format is signed without a check,
+    // anything else without a valid signature is flagged
+    if is_leaderboard_migrated {
+        integrity
+            .update(|integrity| {
+                integrity.sign_leaderboard(leaderboard, &HashSet::new(), &HashSet::new())
+            })
+            .expect("failed to sign migrated leaderboard");
+    }
+    if is_ach_store_migrated {
+        integrity
+            .update(|integrity| integrity.sign_achievement_store(store, &HashSet::new()))
+            .expect("failed to sign migrated achievements");
+    }
+    let report = IntegrityReport::new(&integrity, leaderboard, store);
+    (integrity, report)
+}
+
+fn handle_data_changes(
+    leaderboard: Res<Persistent<Leaderboard>>,
+    ach_store: Res<Persistent<AchievementStore>>,
+    report: Res<IntegrityReport>,
+    backup_cache: Res<BackupCache<Leaderboard>>,
+    mut integrity: ResMut<Persistent<IntegrityStore>>,
+) {
+    if leaderboard.is_changed() || report.is_changed() {
+        integrity
+            .update(|integrity| {
+                integrity.sign_leaderboard(
+                    &leaderboard,
+                    &report.flagged_records,
+                    &leaderboard::fetch_backed_up_uids(&backup_cache),
+                )
+            })
+            .expect("failed to sign leaderboard");
+    }
+    if ach_store.is_changed() || report.is_changed() {
+        integrity
+            .update(|integrity| {
+                integrity.sign_achievement_store(&ach_store, &report.flagged_achievements)
+            })
+            .expect("failed to sign achievements");
+    }
+}
+
+pub struct IntegrityPlugin;
+
+impl Plugin for IntegrityPlugin {
+    fn build(&self, app: &mut App) {
+        let (integrity, report) = load_integrity(
+            app.world.resource::<Persistent<Leaderboard>>(),
+            app.world.resource::<Persistent<AchievementStore>>(),
+            app.world.contains_resource::<Migrated<Leaderboard>>(),
+            app.world.contains_resource::<Migrated<AchievementStore>>(),
+        );
+        app.insert_resource(integrity)
+            .insert_resource(report)
+            .add_systems(PostUpdate, handle_data_changes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_record(index: u32) -> LeaderboardRecord {
+        LeaderboardRecord {
+            score: 100 + index,
+            started_at: format!("2024-01-01_00:00:{:02}.000000000", index),
+            ..default()
+        }
+    }
+
+    #[test]
+    fn restored_records_keep_backed_up_signatures() {
+        let records = vec![build_record(0), build_record(1)];
+        let leaderboard = Leaderboard::from_records(records.clone());
+        let trimmed = Leaderboard::from_records(vec![records[0].clone()]);
+        let backed_up_uids: HashSet<String> = leaderboard
+            .records()
+            .iter()
+            .map(|record| record.uid().to_string())
+            .collect();
+
+        let mut integrity = IntegrityStore::default();
+        integrity.sign_leaderboard(&leaderboard, &HashSet::new(), &HashSet::new());
+        integrity.sign_leaderboard(&trimmed, &HashSet::new(), &backed_up_uids);
+        assert!(records.iter().all(|record| integrity.verify_record(record)));
+
+        integrity.sign_leaderboard(&trimmed, &HashSet::new(), &HashSet::new());
+        assert!(integrity.verify_record(&records[0]));
+        assert!(!integrity.verify_record(&records[1]));
+    }
+
+    fn build_signed_data() -> (Leaderboard, AchievementStore, IntegrityStore) {
+        let leaderboard = Leaderboard::from_records(vec![build_record(0), build_record(1)]);
+        let mut store = AchievementStore::default();
+        store.mark_done("zero_undo");
+        store.add_progress("perfect_match_total", 3);
+        let mut integrity = IntegrityStore::default().with_key("test key");
+        integrity.sign_leaderboard(&leaderboard, &HashSet::new(), &HashSet::new());
+        integrity.sign_achievement_store(&store, &HashSet::new());
+        (leaderboard, store, integrity)
+    }
+
+    #[test]
+    fn untouched_data_passes_verification() {
+        let (leaderboard, store, integrity) = build_signed_data();
+        assert!(IntegrityReport::new(&integrity, &leaderboard, &store).is_empty());
+    }
+
+    #[test]
+    fn edited_record_is_flagged() {
+        let (leaderboard, store, integrity) = build_signed_data();
+        let mut records = leaderboard.records().to_vec();
+        records[0].score += 1;
+        assert!(!integrity.verify_record(&records[0]));
+        assert!(integrity.verify_record(&records[1]));
+        let report = IntegrityReport::new(
+            &integrity,
+            &Leaderboard::from_records(records.clone()),
+            &store,
+        );
+        assert!(report.is_record_flagged(records[0].uid()));
+        assert!(!report.is_record_flagged(records[1].uid()));
+        assert!(report.flagged_achievements.is_empty());
+    }
+
+    #[test]
+    fn edited_achievement_is_flagged() {
+        let (leaderboard, _, integrity) = build_signed_data();
+        let mut store = AchievementStore::default();
+        store.mark_done("zero_undo");
+        store.add_progress("perfect_match_total", 3);
+        store.mark_done("zero_repaint");
+        let mut record = store.fetch_record("zero_undo");
+        record.done_at = String::from("2000-01-01_00:00:00.000000000");
+        assert!(!integrity.verify_achievement(&record));
+        let report = IntegrityReport::new(&integrity, &leaderboard, &store);
+        assert!(report.is_achievement_flagged("zero_undo"));
+        assert!(report.is_achievement_flagged("zero_repaint"));
+        assert!(!report.is_achievement_flagged("perfect_match_total"));
+        assert!(report.flagged_records.is_empty());
+    }
+
+    #[test]
+    fn edited_progress_is_flagged() {
+        let (leaderboard, mut store, integrity) = build_signed_data();
+        assert!(integrity.verify_progress("perfect_match_total", 3));
+        assert!(!integrity.verify_progress("perfect_match_total", 4));
+        store.add_progress("perfect_match_total", 1);
+        let report = IntegrityReport::new(&integrity, &leaderboard, &store);
+        assert!(report.is_achievement_flagged("perfect_match_total"));
+        assert!(!report.is_achievement_flagged("zero_undo"));
+    }
+
+    #[test]
+    fn unsigned_data_is_flagged() {
+        let (leaderboard, store, _) = build_signed_data();
+        let integrity = IntegrityStore::default().with_key("test key");
+        let report = IntegrityReport::new(&integrity, &leaderboard, &store);
+        assert_eq!(report.flagged_records.len(), leaderboard.records().len());
+        assert!(report.is_achievement_flagged("zero_undo"));
+        assert!(report.is_achievement_flagged("perfect_match_total"));
+    }
+}
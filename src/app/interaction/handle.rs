@@ -90,6 +90,7 @@ pub fn handle_switch_interaction(
                 ),
                 width_start: WAVE_START_W,
                 width_end: WAVE_END_W,
+                polylines: vec![],
             },
             IaAnimeEffect,
         );
@@ -133,6 +134,7 @@ pub fn handle_slider_interaction(
                 ),
                 width_start: WAVE_START_W,
                 width_end: WAVE_END_W,
+                polylines: vec![],
             },
             IaAnimeEffect,
         );
@@ -175,6 +177,7 @@ pub fn handle_link_interaction(
                 ),
                 width_start: WAVE_START_W,
                 width_end: WAVE_END_W,
+                polylines: vec![],
             },
             IaAnimeEffect,
         );
@@ -241,6 +244,7 @@ fn draw_focus(commands: &mut Commands, target: FocusTarget) {
                 ),
                 width_start: WAVE_START_W,
                 width_end: WAVE_END_W,
+                polylines: vec![],
             },
             IaAnimeEffect,
         );
@@ -257,6 +261,7 @@ fn draw_focus(commands: &mut Commands, target: FocusTarget) {
                 ),
                 width_start: WAVE_START_W,
                 width_end: WAVE_END_W,
+                polylines: vec![],
             },
             IaAnimeEffect,
         );
@@ -11,8 +11,11 @@ pub enum KeyBindingMode {
     Navgation,
     Gaming,
     Keyboard,
+    Drawing,
 }
 
+const JOYSTICK_UI_DEADZONE: f32 = 0.36;
+
 #[derive(Resource, Default)]
 pub struct KeyBindingConfig {
     pub mode: KeyBindingMode,
@@ -26,7 +29,10 @@ impl Plugin for KeyBindingPlugin {
             mode: KeyBindingMode::Navgation,
         })
         .add_systems(Startup, setup_input_mapping)
-        .add_systems(Update, handle_key_binding.before(NavRequestSystem));
+        .add_systems(
+            Update,
+            (sync_gamepad_navigation, handle_key_binding).before(NavRequestSystem),
+        );
     }
 }
 
@@ -36,12 +42,25 @@ fn setup_input_mapping(mut input_mapping: ResMut<InputMapping>) {
     input_mapping.focus_follows_mouse = true;
 }
 
+fn sync_gamepad_navigation(config: Res<KeyBindingConfig>, mut input_mapping: ResMut<InputMapping>) {
+    if !config.is_changed() {
+        return;
+    }
+    // NOTE: the left stick moves the pen while drawing, keep it from moving the focus
+    input_mapping.joystick_ui_deadzone = match config.mode {
+        KeyBindingMode::Drawing => f32::INFINITY,
+        _ => JOYSTICK_UI_DEADZONE,
+    };
+}
+
 fn handle_key_binding(
     mut requests: EventWriter<NavRequest>,
     input: Res<Input<KeyCode>>,
     config: Res<KeyBindingConfig>,
 ) {
-    move_by_arrow(&mut requests, &input);
+    if config.mode != KeyBindingMode::Drawing {
+        move_by_arrow(&mut requests, &input);
+    }
     match config.mode {
         KeyBindingMode::Navgation => {
             if input.any_just_pressed([KeyCode::Space]) {
@@ -64,7 +83,7 @@ fn handle_key_binding(
             }
         }
         // NOTE: use default key binding only
-        KeyBindingMode::Keyboard => (),
+        KeyBindingMode::Keyboard | KeyBindingMode::Drawing => (),
     }
 }
 
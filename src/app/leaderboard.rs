@@ -1,74 +1,258 @@
 use bevy::prelude::*;
-use bevy_persistent::prelude::*;
+use bevy_persistent::{error::PersistenceError, prelude::*};
+use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::{
+    cmp::Ordering,
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
 
-#[cfg(not(target_arch = "wasm32"))]
-use crate::app;
+use crate::app::{self, backup, status::AppStatus};
 
 pub const MAX_PLAYER_NAME_LENGTH: usize = 12;
 pub const MAX_RECORDS_PER_LIST: usize = 9;
-pub const LEADERBOARD_LISTS: [&str; 5] = [
-    "score",
-    "time",
-    "max_alpha_count",
-    "max_control_chain",
-    "max_hyper_chain",
+const UID_FORMAT: &str = "%Y-%m-%d_%H:%M:%S%.9f";
+pub const LEADERBOARD_LISTS: [LeaderboardField; 5] = [
+    LeaderboardField::Score,
+    LeaderboardField::Time,
+    LeaderboardField::MaxAlphaCount,
+    LeaderboardField::MaxControlChain,
+    LeaderboardField::MaxHyperChain,
 ];
 
+#[derive(Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum LeaderboardField {
+    Score,
+    Time,
+    MaxAlphaCount,
+    MaxControlChain,
+    MaxHyperChain,
+}
+
+impl LeaderboardField {
+    pub fn code(&self) -> &str {
+        match self {
+            LeaderboardField::Score => "score",
+            LeaderboardField::Time => "time",
+            LeaderboardField::MaxAlphaCount => "max_alpha_count",
+            LeaderboardField::MaxControlChain => "max_control_chain",
+            LeaderboardField::MaxHyperChain => "max_hyper_chain",
+        }
+    }
+    pub fn name(&self) -> &str {
+        match self {
+            LeaderboardField::Score => "Score",
+            LeaderboardField::Time => "Time",
+            LeaderboardField::MaxAlphaCount => "Alpha",
+            LeaderboardField::MaxControlChain => "Control",
+            LeaderboardField::MaxHyperChain => "Hyper",
+        }
+    }
+    pub fn is_lower_better(&self) -> bool {
+        matches!(self, LeaderboardField::Time)
+    }
+    pub fn is_better(&self, value: u32, other: u32) -> bool {
+        self.cmp_rank(value, other) == Ordering::Less
+    }
+    // NOTE: the better value comes first
+    pub fn cmp_rank(&self, value: u32, other: u32) -> Ordering {
+        if self.is_lower_better() {
+            value.cmp(&other)
+        } else {
+            other.cmp(&value)
+        }
+    }
+}
+
 #[derive(Resource, Serialize, Deserialize, Clone, Debug, Default)]
 pub struct LeaderboardRecord {
     pub player_name: String,
     pub time: u32,
     pub score: u32,
+    pub match_point: u32,
+    pub undo_count: u32,
+    pub repaint_count: u32,
+    pub max_alpha_count: u32,
+    pub max_control_chain: u32,
+    pub max_hyper_chain: u32,
     pub started_at: String,
     pub ended_at: String,
+    pub seed: Option<u64>,
 }
 
 impl LeaderboardRecord {
+    pub fn from_status(status: &AppStatus, player_name: &str) -> Self {
+        Self {
+            player_name: String::from(player_name),
+            time: status.time,
+            score: status.score,
+            match_point: status.match_point,
+            undo_count: status.undo_count,
+            repaint_count: status.repaint_count,
+            max_alpha_count: status.max_alpha_count,
+            max_control_chain: status.max_control_chain,
+            max_hyper_chain: status.max_hyper_chain,
+            started_at: String::from(status.started_at()),
+            ended_at: String::from(status.ended_at()),
+            seed: Some(status.seed),
+        }
+    }
+
     pub fn uid(&self) -> &str {
         &self.started_at
     }
 
-    pub fn fetch(&self, field: &str) -> u32 {
+    pub fn fetch(&self, field: LeaderboardField) -> u32 {
         match field {
-            "time" => self.time,
-            "score" => self.score,
-            _ => panic!("Invalid field"),
+            LeaderboardField::Score => self.score,
+            LeaderboardField::Time => self.time,
+            LeaderboardField::MaxAlphaCount => self.max_alpha_count,
+            LeaderboardField::MaxControlChain => self.max_control_chain,
+            LeaderboardField::MaxHyperChain => self.max_hyper_chain,
         }
     }
 }
 
-#[derive(Resource, Serialize, Deserialize)]
+#[derive(Resource, Serialize, Deserialize, Clone)]
 pub struct Leaderboard {
     records: Vec<LeaderboardRecord>,
 }
 
 impl Leaderboard {
+    pub fn from_records(records: Vec<LeaderboardRecord>) -> Self {
+        use std::cmp::Reverse;
+        let mut leaderboard = Self { records: vec![] };
+        for record in records {
+            if is_valid_uid(record.uid()) && leaderboard.fetch_record(record.uid()).is_none() {
+                leaderboard.records.push(record);
+            }
+        }
+        leaderboard
+            .records
+            .sort_by_key(|record| (Reverse(record.score), Reverse(record.time)));
+        leaderboard.trim_records();
+        leaderboard
+    }
+
     pub fn store(&mut self, record: LeaderboardRecord) {
         use std::cmp::Reverse;
-        #[cfg(not(target_arch = "wasm32"))]
-        app::screenshot::store_leaderboard_screenshots(record.uid());
+        let uid = record.uid().to_string();
         self.records.push(record);
         self.records
             .sort_by_key(|record| (Reverse(record.score), Reverse(record.time)));
-        if self.records.len() > MAX_RECORDS_PER_LIST * 5 {
-            self.records.pop();
+        let dropped_records = self.trim_records();
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let screenshot_dir = app::screenshot::fetch_screenshots_dir_path();
+            if self.records.iter().any(|record| record.uid() == uid) {
+                app::screenshot::store_leaderboard_screenshots(&screenshot_dir, &uid);
+            }
+            for record in dropped_records.iter() {
+                app::screenshot::delete_leaderboard_screenshots(&screenshot_dir, record.uid());
+            }
         }
+        #[cfg(target_arch = "wasm32")]
+        let _ = (uid, dropped_records);
     }
 
-    pub fn fetch_records(&self, field: &str) -> Vec<LeaderboardRecord> {
+    pub fn records(&self) -> &[LeaderboardRecord] {
+        &self.records
+    }
+
+    pub fn merge_with_screenshot_dir(
+        &mut self,
+        records: Vec<LeaderboardRecord>,
+        screenshot_dir: &Path,
+    ) -> usize {
         use std::cmp::Reverse;
+        let mut merged_count = 0;
+        for record in records {
+            if !is_valid_uid(record.uid())
+                || self
+                    .records
+                    .iter()
+                    .any(|stored| stored.uid() == record.uid())
+            {
+                continue;
+            }
+            self.records.push(record);
+            merged_count += 1;
+        }
+        self.records
+            .sort_by_key(|record| (Reverse(record.score), Reverse(record.time)));
+        let dropped_records = self.trim_records();
+        #[cfg(not(target_arch = "wasm32"))]
+        for record in dropped_records.iter() {
+            app::screenshot::delete_leaderboard_screenshots(screenshot_dir, record.uid());
+        }
+        #[cfg(target_arch = "wasm32")]
+        let _ = (dropped_records, screenshot_dir);
+        merged_count
+    }
+
+    pub fn fetch_record(&self, uid: &str) -> Option<LeaderboardRecord> {
+        self.records
+            .iter()
+            .find(|record| record.uid() == uid)
+            .cloned()
+    }
+
+    pub fn delete(&mut self, uid: &str) {
+        self.records.retain(|record| record.uid() != uid);
+        #[cfg(not(target_arch = "wasm32"))]
+        app::screenshot::delete_leaderboard_screenshots(
+            &app::screenshot::fetch_screenshots_dir_path(),
+            uid,
+        );
+    }
+
+    fn trim_records(&mut self) -> Vec<LeaderboardRecord> {
+        let ranked_uids: HashSet<String> = LEADERBOARD_LISTS
+            .iter()
+            .flat_map(|field| self.fetch_records(*field))
+            .map(|record| record.uid().to_string())
+            .collect();
+        let (kept_records, dropped_records) = self
+            .records
+            .drain(..)
+            .partition(|record| ranked_uids.contains(record.uid()));
+        self.records = kept_records;
+        dropped_records
+    }
+
+    pub fn fetch_records(&self, field: LeaderboardField) -> Vec<LeaderboardRecord> {
+        let tie_field = match field {
+            LeaderboardField::Score => LeaderboardField::Time,
+            _ => LeaderboardField::Score,
+        };
         let mut records = self.records.clone();
-        records.sort_by_key(|record| match field {
-            "time" => (Reverse(record.time), Reverse(record.score)),
-            "score" => (Reverse(record.score), Reverse(record.time)),
-            _ => panic!("Invalid record field"),
+        records.sort_by(|a, b| {
+            field
+                .cmp_rank(a.fetch(field), b.fetch(field))
+                .then_with(|| tie_field.cmp_rank(a.fetch(tie_field), b.fetch(tie_field)))
         });
         records.into_iter().take(MAX_RECORDS_PER_LIST).collect()
     }
 
-    pub fn rank(&self, field: &str, value: u32) -> u8 {
+    pub fn fetch_ranked_records(&self, field: LeaderboardField) -> Vec<(u8, LeaderboardRecord)> {
+        let mut list_rank = 1;
+        let mut prev_value: u32 = 0;
+        self.fetch_records(field)
+            .into_iter()
+            .enumerate()
+            .map(|(i, record)| {
+                let list_value = record.fetch(field);
+                if i == 0 || field.is_better(prev_value, list_value) {
+                    list_rank = i + 1;
+                    prev_value = list_value;
+                }
+                (list_rank as u8, record)
+            })
+            .collect()
+    }
+
+    pub fn rank(&self, field: LeaderboardField, value: u32) -> u8 {
         let records = self.fetch_records(field);
         if records.is_empty() {
             return 1;
@@ -81,11 +265,11 @@ impl Leaderboard {
                 if i == 0 {
                     list_rank = 1;
                     prev_value = list_value;
-                } else if list_value < prev_value {
+                } else if field.is_better(prev_value, list_value) {
                     list_rank = i + 1;
                     prev_value = list_value;
                 }
-                if value >= list_value {
+                if !field.is_better(list_value, value) {
                     return list_rank as u8;
                 }
             }
@@ -93,7 +277,7 @@ impl Leaderboard {
         0
     }
 
-    pub fn target(&self, field: &str, value: u32) -> (u8, u32, u32) {
+    pub fn target(&self, field: LeaderboardField, value: u32) -> (u8, u32, u32) {
         let records = self.fetch_records(field);
         if records.is_empty() {
             return (0, 0, 0);
@@ -104,13 +288,13 @@ impl Leaderboard {
             if let Some(record) = records.get(i) {
                 let list_value = record.fetch(field);
                 if i == 0 {
-                    if value >= list_value {
+                    if !field.is_better(list_value, value) {
                         return (0, 0, 0);
                     }
                     list_rank = 1;
                     prev_value = list_value;
-                } else if list_value < prev_value {
-                    if value >= list_value {
+                } else if field.is_better(prev_value, list_value) {
+                    if !field.is_better(list_value, value) {
                         return (list_rank as u8, prev_value, list_value);
                     }
                     list_rank = i + 1;
@@ -121,13 +305,13 @@ impl Leaderboard {
         (list_rank as u8, prev_value, 0)
     }
 
-    pub fn is_new_in_list(&self, field: &str, value: u32) -> bool {
+    pub fn is_new_in_list(&self, field: LeaderboardField, value: u32) -> bool {
         let records = self.fetch_records(field);
         if records.len() < MAX_RECORDS_PER_LIST {
             return true;
         }
         let last_value = records[MAX_RECORDS_PER_LIST - 1].fetch(field);
-        last_value < value
+        field.is_better(value, last_value)
     }
 
     pub fn is_new_record(&self, record: &LeaderboardRecord) -> bool {
@@ -140,25 +324,360 @@ impl Leaderboard {
     }
 }
 
+pub fn is_valid_uid(uid: &str) -> bool {
+    NaiveDateTime::parse_from_str(uid, UID_FORMAT).is_ok()
+}
+
 pub struct LeaderboardPlugin;
 
 impl Plugin for LeaderboardPlugin {
     fn build(&self, app: &mut App) {
-        let config_dir = dirs::config_dir()
-            .map(|native_config_dir| native_config_dir.join(app::APP_CODE))
-            .unwrap_or(Path::new("local").join("configuration"));
-
-        app.insert_resource(
-            Persistent::<Leaderboard>::builder()
-                .name("leaderboard")
-                .format(StorageFormat::Bincode)
-                .path(config_dir.join("leaderboard.bin"))
-                .default(Leaderboard { records: vec![] })
-                .revertible(true)
-                .revert_to_default_on_deserialization_errors(true)
-                .build()
-                .expect("failed to initialize variables"),
+        backup::insert_loaded(app, load_leaderboard());
+        app.insert_resource(load_backup_cache())
+            .insert_resource(LeaderboardRecord::default())
+            .add_systems(PostUpdate, backup::rotate_cached_backups::<Leaderboard>);
+    }
+}
+
+fn fetch_leaderboard_path() -> PathBuf {
+    dirs::config_dir()
+        .map(|native_config_dir| native_config_dir.join(app::APP_CODE))
+        .unwrap_or(Path::new("local").join("configuration"))
+        .join("leaderboard.bin")
+}
+
+pub fn load_backup_cache() -> backup::BackupCache<Leaderboard> {
+    backup::BackupCache::load(&fetch_leaderboard_path(), |path| {
+        build_leaderboard(path, false)
+    })
+}
+
+pub fn fetch_backed_up_uids(
+    backup_cache: &backup::BackupCache<Leaderboard>,
+) -> bevy::utils::HashSet<String> {
+    backup_cache
+        .backups()
+        .flat_map(|leaderboard| leaderboard.records().iter())
+        .map(|record| record.uid().to_string())
+        .collect()
+}
+
+pub fn load_leaderboard() -> backup::Loaded<Leaderboard> {
+    let path = fetch_leaderboard_path();
+    match backup::load_with_backups(
+        "leaderboard",
+        &path,
+        |path| build_leaderboard(path, false),
+        migrate_leaderboard,
+    ) {
+        Ok(loaded) => loaded,
+        Err(message) => backup::Loaded {
+            persistent: build_leaderboard(&path, true).expect("failed to initialize variables"),
+            notice: Some(message),
+            is_migrated: false,
+        },
+    }
+}
+
+fn build_leaderboard(
+    path: &Path,
+    revert_on_errors: bool,
+) -> Result<Persistent<Leaderboard>, PersistenceError> {
+    Persistent::<Leaderboard>::builder()
+        .name("leaderboard")
+        .format(StorageFormat::Bincode)
+        .path(path)
+        .default(Leaderboard { records: vec![] })
+        .revertible(true)
+        .revert_to_default_on_deserialization_errors(revert_on_errors)
+        .build()
+}
+
+#[derive(Resource, Serialize, Deserialize)]
+struct LegacyLeaderboard {
+    records: Vec<LegacyLeaderboardRecord>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+struct LegacyLeaderboardRecord {
+    player_name: String,
+    time: u32,
+    score: u32,
+    started_at: String,
+    ended_at: String,
+}
+
+impl From<LegacyLeaderboardRecord> for LeaderboardRecord {
+    fn from(record: LegacyLeaderboardRecord) -> Self {
+        Self {
+            player_name: record.player_name,
+            time: record.time,
+            score: record.score,
+            started_at: record.started_at,
+            ended_at: record.ended_at,
+            ..default()
+        }
+    }
+}
+
+fn migrate_leaderboard(path: &Path) -> Option<Persistent<Leaderboard>> {
+    let records: Vec<LeaderboardRecord> = Persistent::<LegacyLeaderboard>::builder()
+        .name("legacy leaderboard")
+        .format(StorageFormat::Bincode)
+        .path(path)
+        .default(LegacyLeaderboard { records: vec![] })
+        .build()
+        .ok()?
+        .get()
+        .records
+        .iter()
+        .cloned()
+        .map(LeaderboardRecord::from)
+        .collect();
+    let mut leaderboard = build_leaderboard(path, true).expect("failed to initialize variables");
+    leaderboard
+        .set(Leaderboard { records })
+        .expect("failed to migrate leaderboard");
+    Some(leaderboard)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_uid(index: u32) -> String {
+        format!("2024-01-01_00:00:{:02}.000000000", index)
+    }
+
+    #[test]
+    fn baseline_leaderboard_migrates_every_record() {
+        let path =
+            std::env::temp_dir().join(format!("gaia_s_pen_leaderboard_{}.bin", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let legacy_records: Vec<LegacyLeaderboardRecord> = (0..3)
+            .map(|i| LegacyLeaderboardRecord {
+                player_name: format!("player{}", i),
+                time: 100 + i,
+                score: 1000 * (i + 1),
+                started_at: build_uid(i),
+                ended_at: build_uid(i + 10),
+            })
+            .collect();
+        let mut legacy = Persistent::<LegacyLeaderboard>::builder()
+            .name("legacy leaderboard")
+            .format(StorageFormat::Bincode)
+            .path(&path)
+            .default(LegacyLeaderboard { records: vec![] })
+            .build()
+            .unwrap();
+        legacy
+            .set(LegacyLeaderboard {
+                records: legacy_records.clone(),
+            })
+            .unwrap();
+
+        assert!(build_leaderboard(&path, false).is_err());
+        let migrated = migrate_leaderboard(&path).unwrap();
+        assert_eq!(migrated.records.len(), legacy_records.len());
+        for legacy_record in legacy_records.iter() {
+            let record = migrated
+                .records
+                .iter()
+                .find(|record| record.uid() == legacy_record.started_at)
+                .unwrap();
+            assert_eq!(record.player_name, legacy_record.player_name);
+            assert_eq!(record.time, legacy_record.time);
+            assert_eq!(record.score, legacy_record.score);
+            assert_eq!(record.started_at, legacy_record.started_at);
+            assert_eq!(record.ended_at, legacy_record.ended_at);
+            assert_eq!(record.seed, None);
+        }
+        let reloaded = build_leaderboard(&path, false).unwrap();
+        assert_eq!(reloaded.records.len(), legacy_records.len());
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn rank_handles_every_field() {
+        let empty = Leaderboard { records: vec![] };
+        for field in LEADERBOARD_LISTS {
+            for value in [0, 1, 5, 100, u32::MAX] {
+                assert_eq!(empty.rank(field, value), 1, "{:?}", field);
+                assert_eq!(empty.target(field, value), (0, 0, 0), "{:?}", field);
+                assert!(empty.is_new_in_list(field, value), "{:?}", field);
+            }
+        }
+        let full = Leaderboard {
+            records: (1..=MAX_RECORDS_PER_LIST as u32)
+                .map(|i| LeaderboardRecord {
+                    score: 100 * i,
+                    time: 10 * i,
+                    max_alpha_count: i,
+                    max_control_chain: i,
+                    max_hyper_chain: 5,
+                    started_at: build_uid(i),
+                    ..default()
+                })
+                .collect(),
+        };
+        // (field, value, rank, target, is_new_in_list)
+        let cases = [
+            (LeaderboardField::Score, 1000, 1, (0, 0, 0), true),
+            (LeaderboardField::Score, 850, 2, (1, 900, 800), true),
+            (LeaderboardField::Score, 100, 9, (8, 200, 100), false),
+            (LeaderboardField::Score, 50, 0, (9, 100, 0), false),
+            (LeaderboardField::Time, 5, 1, (0, 0, 0), true),
+            (LeaderboardField::Time, 15, 2, (1, 10, 20), true),
+            (LeaderboardField::Time, 90, 9, (8, 80, 90), false),
+            (LeaderboardField::Time, 95, 0, (9, 90, 0), false),
+            (LeaderboardField::MaxAlphaCount, 10, 1, (0, 0, 0), true),
+            (LeaderboardField::MaxAlphaCount, 5, 5, (4, 6, 5), true),
+            (LeaderboardField::MaxAlphaCount, 0, 0, (9, 1, 0), false),
+            (LeaderboardField::MaxControlChain, 9, 1, (0, 0, 0), true),
+            (LeaderboardField::MaxControlChain, 1, 9, (8, 2, 1), false),
+            (LeaderboardField::MaxHyperChain, 6, 1, (0, 0, 0), true),
+            (LeaderboardField::MaxHyperChain, 5, 1, (0, 0, 0), false),
+            (LeaderboardField::MaxHyperChain, 4, 0, (1, 5, 0), false),
+        ];
+        for (field, value, rank, target, is_new) in cases {
+            assert_eq!(full.rank(field, value), rank, "{:?} {}", field, value);
+            assert_eq!(full.target(field, value), target, "{:?} {}", field, value);
+            assert_eq!(
+                full.is_new_in_list(field, value),
+                is_new,
+                "{:?} {}",
+                field,
+                value
+            );
+        }
+        let record = LeaderboardRecord {
+            time: 1000,
+            started_at: build_uid(59),
+            ..default()
+        };
+        assert!(empty.is_new_record(&record));
+        assert!(!full.is_new_record(&record));
+    }
+
+    #[test]
+    fn time_ranks_the_fastest_drawing_first() {
+        let leaderboard = Leaderboard {
+            records: (0..MAX_RECORDS_PER_LIST as u32 + 1)
+                .map(|i| LeaderboardRecord {
+                    time: 100 - i,
+                    ..build_scored_record(i, 1000 + i)
+                })
+                .collect(),
+        };
+        let times: Vec<u32> = leaderboard
+            .fetch_ranked_records(LeaderboardField::Time)
+            .into_iter()
+            .map(|(rank, record)| {
+                assert_eq!(rank as u32, record.time - 90);
+                record.time
+            })
+            .collect();
+        assert_eq!(times.first(), Some(&91));
+        assert!(times.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(leaderboard.is_new_record(&LeaderboardRecord {
+            time: 50,
+            ..build_scored_record(30, 0)
+        }));
+        assert_eq!(leaderboard.rank(LeaderboardField::Time, 92), 2);
+    }
+
+    fn build_scored_record(index: u32, score: u32) -> LeaderboardRecord {
+        LeaderboardRecord {
+            score,
+            started_at: build_uid(index),
+            ..default()
+        }
+    }
+
+    #[test]
+    fn trim_keeps_records_ranked_in_any_list() {
+        let mut records: Vec<LeaderboardRecord> = (0..MAX_RECORDS_PER_LIST as u32)
+            .map(|i| build_scored_record(i, 1000 + i))
+            .collect();
+        let chain_record = LeaderboardRecord {
+            max_hyper_chain: 5,
+            ..build_scored_record(20, 1)
+        };
+        records.extend([chain_record, build_scored_record(21, 1)]);
+        let mut leaderboard = Leaderboard { records };
+        let dropped_records = leaderboard.trim_records();
+        assert_eq!(leaderboard.records.len(), MAX_RECORDS_PER_LIST + 1);
+        assert!(leaderboard
+            .records
+            .iter()
+            .any(|record| record.uid() == build_uid(20)));
+        assert_eq!(dropped_records.len(), 1);
+        assert_eq!(dropped_records[0].uid(), build_uid(21));
+    }
+
+    #[test]
+    fn trim_drops_ties_past_the_last_rank() {
+        let mut leaderboard = Leaderboard {
+            records: (0..MAX_RECORDS_PER_LIST as u32 + 1)
+                .map(|i| build_scored_record(i, 100))
+                .collect(),
+        };
+        leaderboard.trim_records();
+        assert_eq!(leaderboard.records.len(), MAX_RECORDS_PER_LIST);
+        for field in LEADERBOARD_LISTS {
+            let ranked_records = leaderboard.fetch_ranked_records(field);
+            assert_eq!(ranked_records.len(), MAX_RECORDS_PER_LIST);
+            assert!(ranked_records.iter().all(|(rank, _)| *rank == 1));
+        }
+        assert!(!leaderboard.is_new_record(&build_scored_record(30, 100)));
+        assert!(leaderboard.is_new_record(&build_scored_record(30, 101)));
+    }
+
+    #[test]
+    fn trim_deletes_screenshots_of_dropped_records() {
+        let screenshot_dir =
+            std::env::temp_dir().join(format!("gaia_s_pen_screenshots_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&screenshot_dir);
+        let low_record = LeaderboardRecord {
+            score: 1,
+            time: 1000,
+            started_at: build_uid(0),
+            ..default()
+        };
+        let high_records: Vec<LeaderboardRecord> = (1..=MAX_RECORDS_PER_LIST as u32)
+            .map(|i| LeaderboardRecord {
+                score: 1000 + i,
+                max_alpha_count: 1,
+                max_control_chain: 1,
+                max_hyper_chain: 1,
+                time: 1,
+                started_at: build_uid(i),
+                ..default()
+            })
+            .collect();
+        let mut leaderboard = Leaderboard {
+            records: vec![low_record.clone()],
+        };
+        let fetch_paths =
+            |uid: &str| app::screenshot::fetch_leaderboard_screenshot_paths(&screenshot_dir, uid);
+        app::screenshot::import_leaderboard_screenshot(
+            &screenshot_dir,
+            low_record.uid(),
+            "score",
+            b"low",
+        );
+        app::screenshot::import_leaderboard_screenshot(
+            &screenshot_dir,
+            high_records[0].uid(),
+            "score",
+            b"high",
         );
-        app.insert_resource(LeaderboardRecord::default());
+        assert_eq!(fetch_paths(low_record.uid()).len(), 1);
+
+        leaderboard.merge_with_screenshot_dir(high_records.clone(), &screenshot_dir);
+        assert!(leaderboard.fetch_record(low_record.uid()).is_none());
+        assert!(fetch_paths(low_record.uid()).is_empty());
+        assert_eq!(fetch_paths(high_records[0].uid()).len(), 1);
+        let _ = std::fs::remove_dir_all(&screenshot_dir);
     }
 }
@@ -0,0 +1,242 @@
+use crate::app::stroke;
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::f32::consts::PI;
+
+const AXIOM: &str = "X";
+const BRANCH_RULES: [&str; 4] = ["F[+X]F[-X]", "F[-X]F[+X]", "F[+X]FX", "F[-X]FX"];
+const STEM_RULE: &str = "FF";
+const ANGLE_JITTER_RATIO: f32 = 0.25;
+const BRANCH_SCALE: f32 = 0.72;
+const LEAF_LENGTH_RATIO: f32 = 1.4;
+const LEAF_WIDTH_RATIO: f32 = 0.4;
+const LEAF_SAMPLE_COUNT: usize = 8;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct PlantParam {
+    pub seed: u64,
+    pub depth: u8,
+    pub branch_angle: f32,
+    pub leaf_density: f32,
+    pub segment_length: f32,
+}
+
+impl Default for PlantParam {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            depth: 3,
+            branch_angle: 28.0,
+            leaf_density: 0.6,
+            segment_length: 40.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum PlantPartKind {
+    Stem,
+    Branch,
+    Leaf,
+}
+
+#[derive(Clone, Debug)]
+pub struct PlantPart {
+    pub kind: PlantPartKind,
+    pub level: usize,
+    pub points: Vec<Vec2>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Plant {
+    pub param: PlantParam,
+    pub parts: Vec<PlantPart>,
+}
+
+impl Plant {
+    pub fn generate(param: PlantParam) -> Self {
+        let mut rng = ChaCha8Rng::seed_from_u64(param.seed);
+        let sentence = expand(&mut rng, param.depth);
+        let parts = interpret(&mut rng, &sentence, &param);
+        Self { param, parts }
+    }
+    pub fn polylines(&self) -> Vec<Vec<Vec2>> {
+        self.parts.iter().map(|part| part.points.clone()).collect()
+    }
+    pub fn growth_polylines(&self) -> Vec<Vec<Vec2>> {
+        let mut parts: Vec<&PlantPart> = self.parts.iter().collect();
+        parts.sort_by_key(|part| part.level);
+        parts.iter().map(|part| part.points.clone()).collect()
+    }
+    pub fn path(&self) -> Path {
+        stroke::build_polyline_path(&self.polylines())
+    }
+    pub fn bounds(&self) -> Rect {
+        let mut rect = Rect {
+            min: Vec2::MAX,
+            max: Vec2::MIN,
+        };
+        for pos in self.parts.iter().flat_map(|part| part.points.iter()) {
+            rect.min = rect.min.min(*pos);
+            rect.max = rect.max.max(*pos);
+        }
+        if rect.min.x > rect.max.x {
+            Rect::default()
+        } else {
+            rect
+        }
+    }
+    pub fn fit(mut self, size: Vec2) -> Self {
+        let bounds = self.bounds();
+        let extent = bounds.size();
+        if extent.x <= 0.0 && extent.y <= 0.0 {
+            return self;
+        }
+        let scale = (size.x / extent.x.max(f32::EPSILON)).min(size.y / extent.y.max(f32::EPSILON));
+        let center = bounds.center();
+        for part in self.parts.iter_mut() {
+            for pos in part.points.iter_mut() {
+                *pos = (*pos - center) * scale;
+            }
+        }
+        self
+    }
+}
+
+fn expand(rng: &mut ChaCha8Rng, depth: u8) -> String {
+    let mut sentence = String::from(AXIOM);
+    for _ in 0..depth {
+        sentence = sentence
+            .chars()
+            .map(|symbol| match symbol {
+                'X' => BRANCH_RULES[rng.gen_range(0..BRANCH_RULES.len())].to_string(),
+                'F' => STEM_RULE.to_string(),
+                _ => symbol.to_string(),
+            })
+            .collect();
+    }
+    sentence
+}
+
+#[derive(Clone, Copy)]
+struct Turtle {
+    pos: Vec2,
+    heading: f32,
+    level: usize,
+    part_index: Option<usize>,
+}
+
+fn interpret(rng: &mut ChaCha8Rng, sentence: &str, param: &PlantParam) -> Vec<PlantPart> {
+    let angle = param.branch_angle.to_radians();
+    let jitter = angle * ANGLE_JITTER_RATIO;
+    let mut parts: Vec<PlantPart> = vec![];
+    let mut stack: Vec<Turtle> = vec![];
+    let mut turtle = Turtle {
+        pos: Vec2::ZERO,
+        heading: PI / 2.0,
+        level: 0,
+        part_index: None,
+    };
+    for symbol in sentence.chars() {
+        match symbol {
+            'F' => {
+                let length = param.segment_length * BRANCH_SCALE.powi(turtle.level as i32);
+                let heading = turtle.heading + rng.gen_range(-jitter..=jitter) * 0.5;
+                let next_pos = turtle.pos + Vec2::from_angle(heading) * length;
+                match turtle.part_index {
+                    Some(index) => parts[index].points.push(next_pos),
+                    None => {
+                        parts.push(PlantPart {
+                            kind: if turtle.level == 0 {
+                                PlantPartKind::Stem
+                            } else {
+                                PlantPartKind::Branch
+                            },
+                            level: turtle.level,
+                            points: vec![turtle.pos, next_pos],
+                        });
+                        turtle.part_index = Some(parts.len() - 1);
+                    }
+                }
+                turtle.pos = next_pos;
+            }
+            '+' => turtle.heading += angle + rng.gen_range(-jitter..=jitter),
+            '-' => turtle.heading -= angle + rng.gen_range(-jitter..=jitter),
+            '[' => {
+                stack.push(turtle);
+                turtle.level += 1;
+                turtle.part_index = None;
+            }
+            ']' => {
+                if let Some(prev_turtle) = stack.pop() {
+                    turtle = prev_turtle;
+                }
+            }
+            'X' if rng.gen_range(0.0..1.0) < param.leaf_density => {
+                let length = param.segment_length
+                    * LEAF_LENGTH_RATIO
+                    * BRANCH_SCALE.powi(turtle.level as i32);
+                parts.push(PlantPart {
+                    kind: PlantPartKind::Leaf,
+                    level: turtle.level + 1,
+                    points: build_leaf(turtle.pos, turtle.heading, length),
+                });
+            }
+            _ => (),
+        }
+    }
+    parts
+}
+
+fn build_leaf(base: Vec2, heading: f32, length: f32) -> Vec<Vec2> {
+    let dir = Vec2::from_angle(heading);
+    let tip = base + dir * length;
+    let side = dir.perp() * length * LEAF_WIDTH_RATIO;
+    let middle = base + dir * length * 0.5;
+    let mut points = sample_quadratic(base, middle + side, tip);
+    points.extend(
+        sample_quadratic(tip, middle - side, base)
+            .into_iter()
+            .skip(1),
+    );
+    points
+}
+
+fn sample_quadratic(start: Vec2, ctrl: Vec2, end: Vec2) -> Vec<Vec2> {
+    (0..=LEAF_SAMPLE_COUNT)
+        .map(|i| {
+            let t = i as f32 / LEAF_SAMPLE_COUNT as f32;
+            start.lerp(ctrl, t).lerp(ctrl.lerp(end, t), t)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generate_with_seed(seed: u64) -> Plant {
+        Plant::generate(PlantParam { seed, ..default() })
+    }
+
+    #[test]
+    fn same_seed_gives_same_plant() {
+        let plant = generate_with_seed(42);
+        assert!(!plant.parts.is_empty());
+        assert_eq!(plant.polylines(), generate_with_seed(42).polylines());
+        assert_eq!(
+            plant.growth_polylines(),
+            generate_with_seed(42).growth_polylines()
+        );
+    }
+
+    #[test]
+    fn different_seed_gives_different_plant() {
+        assert_ne!(
+            generate_with_seed(42).polylines(),
+            generate_with_seed(43).polylines()
+        );
+    }
+}
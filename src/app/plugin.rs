@@ -13,6 +13,7 @@ impl Plugin for AppPlugin {
         app.insert_resource(ClearColor(theme::BG_COLOR))
             .insert_resource(AssetMetaCheck::Never)
             .insert_resource(status::AppStatus::default())
+            .init_resource::<backup::BackupNotice>()
             .add_plugins((
                 DefaultPlugins.set(WindowPlugin {
                     primary_window: Some(Window {
@@ -49,11 +50,14 @@ impl PluginGroup for AppSubPlugins {
             .add(settings::SettingsPlugin)
             .add(leaderboard::LeaderboardPlugin)
             .add(achievement::AchievementPlugin)
+            .add(integrity::IntegrityPlugin)
+            .add(sync::SyncPlugin)
             .add(audio::AudioSeAssetPlugin)
             .add(key_binding::KeyBindingPlugin)
             .add(interaction::InteractionPlugin)
             .add(anime_effect::AnimeEffectPlugin)
             .add(cursor::AppCursorPlugin)
             .add(cursor_icon::AppCursorIconPlugin)
+            .add(stroke::StrokePlugin)
     }
 }
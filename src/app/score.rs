@@ -0,0 +1,71 @@
+use crate::app::{shape_match, status::AppStatus};
+
+pub const MATCH_SCORE_RATE: u32 = 10;
+pub const TIME_BONUS_MAX: u32 = 3000;
+pub const TIME_BONUS_LIMIT: u32 = 12000;
+pub const UNDO_PENALTY: u32 = 50;
+pub const REPAINT_PENALTY: u32 = 300;
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum ScoreGrade {
+    S,
+    A,
+    B,
+    C,
+    D,
+}
+
+const GRADE_THRESHOLDS: [(ScoreGrade, u32); 4] = [
+    (ScoreGrade::S, 11000),
+    (ScoreGrade::A, 9000),
+    (ScoreGrade::B, 7000),
+    (ScoreGrade::C, 5000),
+];
+
+impl ScoreGrade {
+    pub fn from_score(score: u32) -> Self {
+        GRADE_THRESHOLDS
+            .iter()
+            .find(|(_, threshold)| score >= *threshold)
+            .map_or(ScoreGrade::D, |(grade, _)| *grade)
+    }
+    pub fn letter(&self) -> &str {
+        match self {
+            ScoreGrade::S => "S",
+            ScoreGrade::A => "A",
+            ScoreGrade::B => "B",
+            ScoreGrade::C => "C",
+            ScoreGrade::D => "D",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ScoreBreakdown {
+    pub match_score: u32,
+    pub time_bonus: u32,
+    pub undo_penalty: u32,
+    pub repaint_penalty: u32,
+    pub total: u32,
+    pub grade: ScoreGrade,
+}
+
+impl ScoreBreakdown {
+    pub fn new(status: &AppStatus) -> Self {
+        let match_score = status.match_point * MATCH_SCORE_RATE;
+        let time_ratio = 1.0 - (status.time as f32 / TIME_BONUS_LIMIT as f32).min(1.0);
+        let match_ratio = status.match_point as f32 / shape_match::MAX_MATCH_POINT as f32;
+        let time_bonus = (TIME_BONUS_MAX as f32 * time_ratio * match_ratio).round() as u32;
+        let undo_penalty = status.undo_count * UNDO_PENALTY;
+        let repaint_penalty = status.repaint_count * REPAINT_PENALTY;
+        let total = (match_score + time_bonus).saturating_sub(undo_penalty + repaint_penalty);
+        Self {
+            match_score,
+            time_bonus,
+            undo_penalty,
+            repaint_penalty,
+            total,
+            grade: ScoreGrade::from_score(total),
+        }
+    }
+}
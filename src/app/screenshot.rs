@@ -1,10 +1,12 @@
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use bevy::render::view::window::screenshot::ScreenshotManager;
 use bevy::window::PrimaryWindow;
 use bevy::{prelude::*, render::texture::*};
 
+use crate::app::{self, leaderboard};
+
 const SCREENSHOT_TYPES: [&str; 2] = ["score", "max_alpha_count"];
 
 pub fn shot_current(
@@ -17,26 +19,74 @@ pub fn shot_current(
         if !screenshot_dir.exists() {
             let _ = fs::create_dir_all(&screenshot_dir);
         }
-        let path = build_screenshot_file_path("current", screenshot_type);
+        let path = build_screenshot_file_path(&screenshot_dir, "current", screenshot_type);
         let _ = screenshot_manager.save_screenshot_to_disk(main_window.single(), path);
     } else {
         panic!("Invalid screenshot type")
     }
 }
 
-pub fn store_leaderboard_screenshots(uid: &str) {
+pub fn store_leaderboard_screenshots(screenshot_dir: &Path, uid: &str) {
     for screenshot_type in SCREENSHOT_TYPES {
-        let src_path = build_screenshot_file_path("current", screenshot_type);
-        let dest_path = build_screenshot_file_path(uid, screenshot_type);
+        let src_path = build_screenshot_file_path(screenshot_dir, "current", screenshot_type);
+        let dest_path = build_screenshot_file_path(screenshot_dir, uid, screenshot_type);
         if src_path.exists() {
             let _ = fs::copy(src_path, dest_path);
         }
     }
 }
 
-pub fn fetch_screenshot_image(uid: &str, screenshot_type: &str) -> Image {
-    let image_path = build_screenshot_file_path(uid, screenshot_type);
-    let image_data = fs::read(image_path).unwrap();
+pub fn delete_leaderboard_screenshots(screenshot_dir: &Path, uid: &str) {
+    for screenshot_type in SCREENSHOT_TYPES {
+        let path = build_screenshot_file_path(screenshot_dir, uid, screenshot_type);
+        if path.exists() {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+pub fn fetch_leaderboard_screenshot_paths(
+    screenshot_dir: &Path,
+    uid: &str,
+) -> Vec<(&'static str, PathBuf)> {
+    SCREENSHOT_TYPES
+        .into_iter()
+        .map(|screenshot_type| {
+            (
+                screenshot_type,
+                build_screenshot_file_path(screenshot_dir, uid, screenshot_type),
+            )
+        })
+        .filter(|(_, path)| path.exists())
+        .collect()
+}
+
+pub fn is_screenshot_type(screenshot_type: &str) -> bool {
+    SCREENSHOT_TYPES.contains(&screenshot_type)
+}
+
+pub fn import_leaderboard_screenshot(
+    screenshot_dir: &Path,
+    uid: &str,
+    screenshot_type: &str,
+    data: &[u8],
+) {
+    if !is_screenshot_type(screenshot_type) || !leaderboard::is_valid_uid(uid) {
+        return;
+    }
+    if !screenshot_dir.exists() {
+        let _ = fs::create_dir_all(screenshot_dir);
+    }
+    let path = build_screenshot_file_path(screenshot_dir, uid, screenshot_type);
+    if !path.exists() {
+        let _ = fs::write(path, data);
+    }
+}
+
+pub fn fetch_screenshot_image(uid: &str, screenshot_type: &str) -> Option<Image> {
+    let image_path =
+        build_screenshot_file_path(&fetch_screenshots_dir_path(), uid, screenshot_type);
+    let image_data = fs::read(image_path).ok()?;
     Image::from_buffer(
         &image_data,
         ImageType::Format(ImageFormat::Png),
@@ -44,16 +94,20 @@ pub fn fetch_screenshot_image(uid: &str, screenshot_type: &str) -> Image {
         true,
         ImageSampler::default(),
     )
-    .unwrap()
+    .ok()
 }
 
-fn fetch_screenshots_dir_path() -> PathBuf {
+pub fn fetch_screenshots_dir_path() -> PathBuf {
     dirs::config_dir()
-        .map(|native_config_dir| native_config_dir.join("chain-reaction").join("screenshots"))
-        .unwrap()
+        .map(|native_config_dir| native_config_dir.join(app::APP_CODE))
+        .unwrap_or(Path::new("local").join("configuration"))
+        .join("screenshots")
 }
 
-fn build_screenshot_file_path(screenshot_uid: &str, screenshot_type: &str) -> PathBuf {
-    let screenshot_dir = fetch_screenshots_dir_path();
+fn build_screenshot_file_path(
+    screenshot_dir: &Path,
+    screenshot_uid: &str,
+    screenshot_type: &str,
+) -> PathBuf {
     screenshot_dir.join(format!("{}_{}.png", screenshot_uid, screenshot_type))
 }
@@ -1,6 +1,6 @@
-use crate::app;
+use crate::app::{self, backup};
 use bevy::prelude::*;
-use bevy_persistent::prelude::*;
+use bevy_persistent::{error::PersistenceError, prelude::*};
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 
@@ -15,6 +15,12 @@ pub struct Settings {
     sensitivity: u8,
     sensitivity_modified: u8,
     last_player: String,
+    #[serde(default)]
+    sync_enabled: bool,
+    #[serde(default)]
+    sync_url: String,
+    #[serde(default)]
+    match_metric: String,
 }
 
 impl Settings {
@@ -24,6 +30,7 @@ impl Settings {
             "bgm" => self.bgm_enabled,
             "se" => self.se_enabled,
             "fullscreen" => self.fullscreen_enabled,
+            "sync" => self.sync_enabled,
             _ => false,
         }
     }
@@ -33,6 +40,7 @@ impl Settings {
             "bgm" => self.bgm_enabled = !self.bgm_enabled,
             "se" => self.se_enabled = !self.se_enabled,
             "fullscreen" => self.fullscreen_enabled = !self.fullscreen_enabled,
+            "sync" => self.sync_enabled = !self.sync_enabled,
             _ => println!("Invalid field"),
         }
     }
@@ -83,6 +91,18 @@ impl Settings {
     pub fn update_last_player(&mut self, name: &str) {
         self.last_player = String::from(name);
     }
+    pub fn fetch_sync_url(&self) -> &str {
+        &self.sync_url
+    }
+    pub fn update_sync_url(&mut self, url: &str) {
+        self.sync_url = String::from(url);
+    }
+    pub fn fetch_match_metric(&self) -> &str {
+        &self.match_metric
+    }
+    pub fn update_match_metric(&mut self, code: &str) {
+        self.match_metric = String::from(code);
+    }
     fn is_value_vaild(value: i8) -> bool {
         (0..=100).contains(&value)
     }
@@ -95,27 +115,48 @@ impl Plugin for SettingsPlugin {
         let config_dir = dirs::config_dir()
             .map(|native_config_dir| native_config_dir.join(app::APP_CODE))
             .unwrap_or(Path::new("local").join("configuration"));
-
-        app.insert_resource(
-            Persistent::<Settings>::builder()
-                .name("settings")
-                .format(StorageFormat::Json)
-                .path(config_dir.join("settings.json"))
-                .default(Settings {
-                    first_run: true,
-                    bgm_enabled: true,
-                    bgm_volume: 50,
-                    se_enabled: true,
-                    se_volume: 50,
-                    fullscreen_enabled: false,
-                    sensitivity: 50,
-                    sensitivity_modified: 10,
-                    last_player: String::from(""),
-                })
-                .revertible(true)
-                .revert_to_default_on_deserialization_errors(true)
-                .build()
-                .expect("failed to initialize variables"),
-        );
+        let path = config_dir.join("settings.json");
+        let loaded = match backup::load_with_backups(
+            "settings",
+            &path,
+            |path| build_settings(path, false),
+            |_| None,
+        ) {
+            Ok(loaded) => loaded,
+            Err(message) => backup::Loaded {
+                persistent: build_settings(&path, true).expect("failed to initialize variables"),
+                notice: Some(message),
+                is_migrated: false,
+            },
+        };
+        backup::insert_loaded(app, loaded);
+        app.add_systems(PostUpdate, backup::rotate_persisted_backups::<Settings>);
     }
 }
+
+fn build_settings(
+    path: &Path,
+    revert_on_errors: bool,
+) -> Result<Persistent<Settings>, PersistenceError> {
+    Persistent::<Settings>::builder()
+        .name("settings")
+        .format(StorageFormat::Json)
+        .path(path)
+        .default(Settings {
+            first_run: true,
+            bgm_enabled: true,
+            bgm_volume: 50,
+            se_enabled: true,
+            se_volume: 50,
+            fullscreen_enabled: false,
+            sensitivity: 50,
+            sensitivity_modified: 10,
+            last_player: String::from(""),
+            sync_enabled: false,
+            sync_url: String::from(""),
+            match_metric: String::from(""),
+        })
+        .revertible(true)
+        .revert_to_default_on_deserialization_errors(revert_on_errors)
+        .build()
+}
@@ -0,0 +1,294 @@
+use bevy::math::Vec2;
+
+pub mod gesture;
+pub mod outline;
+pub mod stroke_order;
+
+pub const MAX_MATCH_POINT: u32 = 1000;
+
+const HAUSDORFF_PERCENTILE: f32 = 0.9;
+
+#[derive(Clone, Copy, Debug)]
+pub struct MatchConfig {
+    pub sample_step: f32,
+    pub tolerance: f32,
+    pub max_distance: f32,
+    pub coverage_radius: f32,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct MatchResult {
+    pub chamfer: f32,
+    pub hausdorff: f32,
+    pub coverage: f32,
+    pub match_point: u32,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum MatchMetricKind {
+    #[default]
+    Outline,
+    Gesture,
+    StrokeOrder,
+}
+
+pub const MATCH_METRIC_KINDS: [MatchMetricKind; 3] = [
+    MatchMetricKind::Outline,
+    MatchMetricKind::Gesture,
+    MatchMetricKind::StrokeOrder,
+];
+
+impl MatchMetricKind {
+    pub fn code(&self) -> &str {
+        fetch_metric(*self).code()
+    }
+    pub fn name(&self) -> &str {
+        fetch_metric(*self).name()
+    }
+    pub fn next(&self) -> Self {
+        let index = MATCH_METRIC_KINDS
+            .iter()
+            .position(|kind| kind == self)
+            .unwrap_or(0);
+        MATCH_METRIC_KINDS[(index + 1) % MATCH_METRIC_KINDS.len()]
+    }
+}
+
+pub fn fetch_metric_kind(code: &str) -> MatchMetricKind {
+    MATCH_METRIC_KINDS
+        .into_iter()
+        .find(|kind| kind.code() == code)
+        .unwrap_or_default()
+}
+
+pub trait MatchMetricBase {
+    fn code(&self) -> &str;
+    fn name(&self) -> &str;
+    fn evaluate(&self, target: &[Vec<Vec2>], drawn: &[Vec<Vec2>]) -> MatchResult;
+}
+
+pub fn fetch_metric(kind: MatchMetricKind) -> &'static dyn MatchMetricBase {
+    match kind {
+        MatchMetricKind::Outline => &outline::MatchMetric,
+        MatchMetricKind::Gesture => &gesture::MatchMetric,
+        MatchMetricKind::StrokeOrder => &stroke_order::MatchMetric,
+    }
+}
+
+pub fn match_polylines(
+    kind: MatchMetricKind,
+    target: &[Vec<Vec2>],
+    drawn: &[Vec<Vec2>],
+) -> MatchResult {
+    fetch_metric(kind).evaluate(target, drawn)
+}
+
+pub fn match_points(
+    target_points: &[Vec2],
+    drawn_points: &[Vec2],
+    config: &MatchConfig,
+) -> MatchResult {
+    if target_points.is_empty() || drawn_points.is_empty() {
+        return MatchResult::default();
+    }
+    let target_dists = nearest_distances(target_points, drawn_points);
+    let drawn_dists = nearest_distances(drawn_points, target_points);
+    let chamfer = (mean(&target_dists) + mean(&drawn_dists)) / 2.0;
+    let hausdorff = percentile(&target_dists, HAUSDORFF_PERCENTILE)
+        .max(percentile(&drawn_dists, HAUSDORFF_PERCENTILE));
+    let covered_count = target_dists
+        .iter()
+        .filter(|dist| **dist <= config.coverage_radius)
+        .count();
+    let coverage = covered_count as f32 / target_dists.len() as f32;
+    let distance_ratio =
+        (to_distance_ratio(chamfer, config) + to_distance_ratio(hausdorff, config)) / 2.0;
+    MatchResult {
+        chamfer,
+        hausdorff,
+        coverage,
+        match_point: to_match_point(distance_ratio * coverage),
+    }
+}
+
+pub fn resample(polylines: &[Vec<Vec2>], step: f32) -> Vec<Vec2> {
+    let mut points: Vec<Vec2> = vec![];
+    for polyline in polylines.iter() {
+        let mut prev_pos = match polyline.first() {
+            Some(pos) => *pos,
+            None => continue,
+        };
+        points.push(prev_pos);
+        let mut remain = step;
+        for pos in polyline.iter().skip(1) {
+            let mut segment_length = prev_pos.distance(*pos);
+            while segment_length >= remain {
+                prev_pos = prev_pos.lerp(*pos, remain / segment_length);
+                points.push(prev_pos);
+                segment_length -= remain;
+                remain = step;
+            }
+            remain -= segment_length;
+            prev_pos = *pos;
+        }
+        if remain < step {
+            points.push(prev_pos);
+        }
+    }
+    points
+}
+
+pub fn to_match_point(ratio: f32) -> u32 {
+    (ratio.clamp(0.0, 1.0) * MAX_MATCH_POINT as f32).round() as u32
+}
+
+fn nearest_distances(from: &[Vec2], to: &[Vec2]) -> Vec<f32> {
+    from.iter()
+        .map(|pos| {
+            to.iter()
+                .map(|to_pos| pos.distance_squared(*to_pos))
+                .fold(f32::MAX, f32::min)
+                .sqrt()
+        })
+        .collect()
+}
+
+pub fn polyline_length(polyline: &[Vec2]) -> f32 {
+    polyline
+        .windows(2)
+        .map(|pair| pair[0].distance(pair[1]))
+        .sum()
+}
+
+pub fn resample_to_count(polyline: &[Vec2], count: usize) -> Vec<Vec2> {
+    let length = polyline_length(polyline);
+    if polyline.len() < 2 || length <= 0.0 || count < 2 {
+        return polyline.first().map_or(vec![], |pos| vec![*pos; count]);
+    }
+    let mut points = resample(&[polyline.to_vec()], length / (count - 1) as f32);
+    points.truncate(count);
+    while points.len() < count {
+        points.push(*polyline.last().unwrap());
+    }
+    points
+}
+
+fn mean(values: &[f32]) -> f32 {
+    values.iter().sum::<f32>() / values.len() as f32
+}
+
+fn percentile(values: &[f32], ratio: f32) -> f32 {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let index = ((sorted.len() - 1) as f32 * ratio).round() as usize;
+    sorted[index]
+}
+
+pub fn to_distance_ratio(distance: f32, config: &MatchConfig) -> f32 {
+    if distance <= config.tolerance {
+        1.0
+    } else {
+        1.0 - ((distance - config.tolerance) / (config.max_distance - config.tolerance)).min(1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(offset: Vec2) -> Vec<Vec<Vec2>> {
+        vec![
+            vec![Vec2::new(0.0, 0.0) + offset, Vec2::new(200.0, 0.0) + offset],
+            vec![
+                Vec2::new(200.0, 0.0) + offset,
+                Vec2::new(200.0, 200.0) + offset,
+            ],
+            vec![
+                Vec2::new(200.0, 200.0) + offset,
+                Vec2::new(0.0, 200.0) + offset,
+            ],
+            vec![Vec2::new(0.0, 200.0) + offset, Vec2::new(0.0, 0.0) + offset],
+        ]
+    }
+
+    #[test]
+    fn identical_polylines_score_max() {
+        let target = square(Vec2::ZERO);
+        for kind in MATCH_METRIC_KINDS {
+            let result = match_polylines(kind, &target, &target);
+            assert_eq!(result.match_point, MAX_MATCH_POINT, "{:?}", kind);
+        }
+    }
+
+    #[test]
+    fn shifted_shape_scores_lower() {
+        let target = square(Vec2::ZERO);
+        let drawn = square(Vec2::new(30.0, 30.0));
+        for kind in [MatchMetricKind::Outline, MatchMetricKind::StrokeOrder] {
+            let result = match_polylines(kind, &target, &drawn);
+            assert!(result.match_point < MAX_MATCH_POINT, "{:?}", kind);
+        }
+    }
+
+    #[test]
+    fn half_covered_shape_scores_lower() {
+        let target = square(Vec2::ZERO);
+        let drawn = target[..2].to_vec();
+        for kind in MATCH_METRIC_KINDS {
+            let result = match_polylines(kind, &target, &drawn);
+            assert!(result.match_point < MAX_MATCH_POINT, "{:?}", kind);
+        }
+    }
+
+    #[test]
+    fn empty_or_single_point_scores_zero() {
+        let target = square(Vec2::ZERO);
+        let single = vec![vec![Vec2::new(100.0, 100.0)]];
+        for kind in MATCH_METRIC_KINDS {
+            assert_eq!(match_polylines(kind, &target, &[]).match_point, 0);
+            assert_eq!(match_polylines(kind, &[], &target).match_point, 0);
+            assert_eq!(match_polylines(kind, &[], &[]).match_point, 0);
+            assert_eq!(match_polylines(kind, &[vec![]], &[vec![]]).match_point, 0);
+            assert_eq!(match_polylines(kind, &target, &single).match_point, 0);
+        }
+    }
+
+    #[test]
+    fn resample_keeps_both_ends_when_step_exceeds_length() {
+        let polylines = vec![vec![Vec2::new(0.0, 0.0), Vec2::new(3.0, 0.0)]];
+        let polyline = polylines[0].clone();
+        assert_eq!(resample(&polylines, 10.0), polyline);
+        assert_eq!(resample_to_count(&polyline, 2), polyline);
+    }
+
+    #[test]
+    fn metric_kind_is_picked_by_code() {
+        for kind in MATCH_METRIC_KINDS {
+            assert_eq!(fetch_metric_kind(kind.code()), kind);
+            assert_ne!(kind.next(), kind);
+        }
+        assert_eq!(fetch_metric_kind(""), MatchMetricKind::Outline);
+        assert_eq!(fetch_metric_kind("unknown"), MatchMetricKind::Outline);
+    }
+
+    #[test]
+    fn gesture_ignores_size_and_position() {
+        let target = square(Vec2::ZERO);
+        let drawn: Vec<Vec<Vec2>> = square(Vec2::new(-100.0, 50.0))
+            .into_iter()
+            .map(|polyline| polyline.into_iter().map(|pos| pos * 3.0).collect())
+            .collect();
+        let result = match_polylines(MatchMetricKind::Gesture, &target, &drawn);
+        assert_eq!(result.match_point, MAX_MATCH_POINT);
+    }
+
+    #[test]
+    fn reversed_stroke_order_scores_lower() {
+        let target = square(Vec2::ZERO);
+        let mut drawn = target.clone();
+        drawn.reverse();
+        let in_order = match_polylines(MatchMetricKind::StrokeOrder, &target, &target);
+        let reversed = match_polylines(MatchMetricKind::StrokeOrder, &target, &drawn);
+        assert!(reversed.match_point < in_order.match_point);
+    }
+}
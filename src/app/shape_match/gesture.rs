@@ -0,0 +1,45 @@
+use crate::app::shape_match::*;
+
+pub struct MatchMetric;
+
+// NOTE: every distance below is in the normalized space where the shape fits in NORMALIZED_SIZE
+const NORMALIZED_SIZE: f32 = 100.0;
+const CONFIG: MatchConfig = MatchConfig {
+    sample_step: 2.0,
+    tolerance: 3.0,
+    max_distance: 25.0,
+    coverage_radius: 8.0,
+};
+
+impl MatchMetricBase for MatchMetric {
+    fn code(&self) -> &str {
+        "gesture"
+    }
+    fn name(&self) -> &str {
+        "Gesture"
+    }
+    fn evaluate(&self, target: &[Vec<Vec2>], drawn: &[Vec<Vec2>]) -> MatchResult {
+        match_points(
+            &resample(&normalize(target), CONFIG.sample_step),
+            &resample(&normalize(drawn), CONFIG.sample_step),
+            &CONFIG,
+        )
+    }
+}
+
+fn normalize(polylines: &[Vec<Vec2>]) -> Vec<Vec<Vec2>> {
+    let points = polylines.iter().flatten();
+    let min = points.clone().fold(Vec2::MAX, |acc, pos| acc.min(*pos));
+    let max = points.fold(Vec2::MIN, |acc, pos| acc.max(*pos));
+    let center = (min + max) / 2.0;
+    let extent = (max - min).max_element();
+    let scale = if extent > 0.0 {
+        NORMALIZED_SIZE / extent
+    } else {
+        1.0
+    };
+    polylines
+        .iter()
+        .map(|polyline| polyline.iter().map(|pos| (*pos - center) * scale).collect())
+        .collect()
+}
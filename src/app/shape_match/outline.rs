@@ -0,0 +1,26 @@
+use crate::app::shape_match::*;
+
+pub struct MatchMetric;
+
+const CONFIG: MatchConfig = MatchConfig {
+    sample_step: 4.0,
+    tolerance: 4.0,
+    max_distance: 40.0,
+    coverage_radius: 12.0,
+};
+
+impl MatchMetricBase for MatchMetric {
+    fn code(&self) -> &str {
+        "outline"
+    }
+    fn name(&self) -> &str {
+        "Outline"
+    }
+    fn evaluate(&self, target: &[Vec<Vec2>], drawn: &[Vec<Vec2>]) -> MatchResult {
+        match_points(
+            &resample(target, CONFIG.sample_step),
+            &resample(drawn, CONFIG.sample_step),
+            &CONFIG,
+        )
+    }
+}
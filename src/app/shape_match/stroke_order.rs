@@ -0,0 +1,65 @@
+use crate::app::shape_match::*;
+
+pub struct MatchMetric;
+
+const MIN_SAMPLE_COUNT: usize = 2;
+const CONFIG: MatchConfig = MatchConfig {
+    sample_step: 4.0,
+    tolerance: 6.0,
+    max_distance: 50.0,
+    coverage_radius: 16.0,
+};
+
+impl MatchMetricBase for MatchMetric {
+    fn code(&self) -> &str {
+        "stroke_order"
+    }
+    fn name(&self) -> &str {
+        "Stroke Order"
+    }
+    fn evaluate(&self, target: &[Vec<Vec2>], drawn: &[Vec<Vec2>]) -> MatchResult {
+        let stroke_count = target.len().max(drawn.len());
+        if target.is_empty() || drawn.is_empty() {
+            return MatchResult::default();
+        }
+        let mut ratio_sum = 0.0;
+        let mut dist_sum = 0.0;
+        let mut max_dist: f32 = 0.0;
+        let mut covered_count = 0;
+        let mut sample_count = 0;
+        for (index, target_stroke) in target.iter().enumerate() {
+            let count = to_sample_count(target_stroke);
+            sample_count += count;
+            let drawn_stroke = match drawn.get(index) {
+                Some(stroke) if !stroke.is_empty() => stroke,
+                _ => continue,
+            };
+            let target_points = resample_to_count(target_stroke, count);
+            let drawn_points = resample_to_count(drawn_stroke, count);
+            let dists: Vec<f32> = target_points
+                .iter()
+                .zip(drawn_points.iter())
+                .map(|(target_pos, drawn_pos)| target_pos.distance(*drawn_pos))
+                .collect();
+            let stroke_dist = dists.iter().sum::<f32>() / count as f32;
+            dist_sum += stroke_dist;
+            max_dist = dists.iter().fold(max_dist, |acc, dist| acc.max(*dist));
+            covered_count += dists
+                .iter()
+                .filter(|dist| **dist <= CONFIG.coverage_radius)
+                .count();
+            ratio_sum += to_distance_ratio(stroke_dist, &CONFIG);
+        }
+        let coverage = covered_count as f32 / sample_count as f32;
+        MatchResult {
+            chamfer: dist_sum / target.len().min(drawn.len()) as f32,
+            hausdorff: max_dist,
+            coverage,
+            match_point: to_match_point(ratio_sum / stroke_count as f32 * coverage.sqrt()),
+        }
+    }
+}
+
+fn to_sample_count(polyline: &[Vec2]) -> usize {
+    ((polyline_length(polyline) / CONFIG.sample_step).ceil() as usize + 1).max(MIN_SAMPLE_COUNT)
+}
@@ -1,5 +1,6 @@
 use bevy::prelude::*;
 use chrono::Local;
+use serde::{Deserialize, Serialize};
 
 #[derive(PartialEq, Default, Debug)]
 pub enum StatusChain {
@@ -9,6 +10,18 @@ pub enum StatusChain {
     Control,
 }
 
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum StatusField {
+    Time,
+    Score,
+    UndoCount,
+    RepaintCount,
+    MatchPoint,
+    MaxAlphaCount,
+    MaxControlChain,
+    MaxHyperChain,
+}
+
 #[derive(Resource, Default, Debug)]
 pub struct AppStatus {
     pub player_name: String,
@@ -20,6 +33,12 @@ pub struct AppStatus {
     pub undo_count: u32,
     pub repaint_count: u32,
     pub match_point: u32,
+    pub max_alpha_count: u32,
+    pub max_control_chain: u32,
+    pub max_hyper_chain: u32,
+    pub control_chain: u32,
+    pub hyper_chain: u32,
+    pub seed: u64,
     started_at: String,
     ended_at: String,
 }
@@ -29,6 +48,27 @@ impl AppStatus {
         &self.started_at
     }
 
+    pub fn started_at(&self) -> &str {
+        &self.started_at
+    }
+
+    pub fn ended_at(&self) -> &str {
+        &self.ended_at
+    }
+
+    pub fn fetch(&self, field: StatusField) -> u32 {
+        match field {
+            StatusField::Time => self.time,
+            StatusField::Score => self.score,
+            StatusField::UndoCount => self.undo_count,
+            StatusField::RepaintCount => self.repaint_count,
+            StatusField::MatchPoint => self.match_point,
+            StatusField::MaxAlphaCount => self.max_alpha_count,
+            StatusField::MaxControlChain => self.max_control_chain,
+            StatusField::MaxHyperChain => self.max_hyper_chain,
+        }
+    }
+
     pub fn mark_timeline(&mut self, timeline_type: &str) {
         let now_dt = Local::now().format("%Y-%m-%d_%H:%M:%S%.9f").to_string();
         match timeline_type {
@@ -0,0 +1,254 @@
+use crate::app::{cursor::AppCursorData, theme::*, ui};
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+use std::time::Duration;
+
+mod input;
+mod plugin;
+mod render;
+
+pub use plugin::StrokePlugin;
+pub use render::{build_line_stroke, build_polyline_path};
+
+pub const PEN_LINE_W: f32 = ui::SPACE_SIZE * 1.6;
+pub const PEN_COLOR: Color = FG_COLOR;
+const PEN_MIN_STEP: f32 = 3.0;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum PenDevice {
+    Mouse,
+    Touch,
+    Keyboard,
+    Gamepad,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct StrokeSample {
+    pub pos: Vec2,
+    pub time_ms: u32,
+}
+
+#[derive(Clone, Debug)]
+pub struct PenStroke {
+    pub device: PenDevice,
+    pub samples: Vec<StrokeSample>,
+}
+
+impl PenStroke {
+    pub fn points(&self) -> Vec<Vec2> {
+        self.samples.iter().map(|sample| sample.pos).collect()
+    }
+
+    pub fn length(&self) -> f32 {
+        self.samples
+            .windows(2)
+            .map(|pair| pair[0].pos.distance(pair[1].pos))
+            .sum()
+    }
+
+    pub fn duration_ms(&self) -> u32 {
+        match (self.samples.first(), self.samples.last()) {
+            (Some(first), Some(last)) => last.time_ms - first.time_ms,
+            _ => 0,
+        }
+    }
+}
+
+#[derive(Event, Debug)]
+pub enum StrokeEvent {
+    Started { index: usize },
+    Ended { index: usize },
+}
+
+#[derive(Resource, Default, Debug)]
+pub struct StrokeStore {
+    strokes: Vec<PenStroke>,
+    undone_strokes: Vec<PenStroke>,
+    is_drawing: bool,
+    started_at: Duration,
+}
+
+impl StrokeStore {
+    pub fn reset(&mut self, now: Duration) {
+        *self = StrokeStore {
+            started_at: now,
+            ..default()
+        };
+    }
+
+    pub fn clear(&mut self) {
+        self.strokes.clear();
+        self.undone_strokes.clear();
+        self.is_drawing = false;
+    }
+
+    pub fn strokes(&self) -> &[PenStroke] {
+        &self.strokes
+    }
+
+    pub fn polylines(&self) -> Vec<Vec<Vec2>> {
+        self.strokes.iter().map(|stroke| stroke.points()).collect()
+    }
+
+    pub fn is_drawing(&self) -> bool {
+        self.is_drawing
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.is_drawing && !self.strokes.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.is_drawing && !self.undone_strokes.is_empty()
+    }
+
+    pub fn undo(&mut self) -> bool {
+        if !self.can_undo() {
+            return false;
+        }
+        if let Some(stroke) = self.strokes.pop() {
+            self.undone_strokes.push(stroke);
+        }
+        true
+    }
+
+    pub fn redo(&mut self) -> bool {
+        if !self.can_redo() {
+            return false;
+        }
+        if let Some(stroke) = self.undone_strokes.pop() {
+            self.strokes.push(stroke);
+        }
+        true
+    }
+
+    fn pen_down(&mut self, pos: Vec2, device: PenDevice, now: Duration) -> usize {
+        let time_ms = self.to_time_ms(now);
+        self.undone_strokes.clear();
+        self.strokes.push(PenStroke {
+            device,
+            samples: vec![StrokeSample { pos, time_ms }],
+        });
+        self.is_drawing = true;
+        self.strokes.len() - 1
+    }
+
+    fn pen_move(&mut self, pos: Vec2, now: Duration) -> bool {
+        if !self.is_drawing {
+            return false;
+        }
+        let time_ms = self.to_time_ms(now);
+        if let Some(stroke) = self.strokes.last_mut() {
+            let is_moved = stroke
+                .samples
+                .last()
+                .map_or(true, |sample| sample.pos.distance(pos) >= PEN_MIN_STEP);
+            if is_moved {
+                stroke.samples.push(StrokeSample { pos, time_ms });
+                return true;
+            }
+        }
+        false
+    }
+
+    fn pen_up(&mut self) -> Option<usize> {
+        if self.is_drawing {
+            self.is_drawing = false;
+            Some(self.strokes.len() - 1)
+        } else {
+            None
+        }
+    }
+
+    fn to_time_ms(&self, now: Duration) -> u32 {
+        now.saturating_sub(self.started_at).as_millis() as u32
+    }
+}
+
+#[derive(Component)]
+pub struct StrokeCanvas {
+    pub size: Vec2,
+    pub is_enabled: bool,
+}
+
+impl StrokeCanvas {
+    pub fn contains(&self, local_pos: Vec2) -> bool {
+        local_pos.x.abs() <= self.size.x / 2.0 && local_pos.y.abs() <= self.size.y / 2.0
+    }
+}
+
+#[derive(Component)]
+pub struct StrokeShape(pub usize);
+
+pub fn to_local_pos(canvas_trans: &GlobalTransform, canvas_pos: Vec2) -> Vec2 {
+    canvas_pos - canvas_trans.translation().truncate()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn draw_stroke(store: &mut StrokeStore, x: f32, now_ms: u64) {
+        store.pen_down(
+            Vec2::new(x, 0.0),
+            PenDevice::Mouse,
+            Duration::from_millis(now_ms),
+        );
+        store.pen_move(Vec2::new(x, 10.0), Duration::from_millis(now_ms + 10));
+        store.pen_up();
+    }
+
+    fn fetch_starts(store: &StrokeStore) -> Vec<f32> {
+        store
+            .strokes()
+            .iter()
+            .map(|stroke| stroke.samples[0].pos.x)
+            .collect()
+    }
+
+    #[test]
+    fn redo_restores_undone_strokes_in_order() {
+        let mut store = StrokeStore::default();
+        for i in 0..3 {
+            draw_stroke(&mut store, i as f32, i * 100);
+        }
+        assert!(store.undo());
+        assert!(store.undo());
+        assert_eq!(fetch_starts(&store), vec![0.0]);
+        assert!(store.redo());
+        assert_eq!(fetch_starts(&store), vec![0.0, 1.0]);
+        assert!(store.redo());
+        assert_eq!(fetch_starts(&store), vec![0.0, 1.0, 2.0]);
+        assert!(!store.redo());
+    }
+
+    #[test]
+    fn new_stroke_clears_redo() {
+        let mut store = StrokeStore::default();
+        draw_stroke(&mut store, 0.0, 0);
+        draw_stroke(&mut store, 1.0, 100);
+        assert!(store.undo());
+        assert!(store.can_redo());
+        draw_stroke(&mut store, 2.0, 200);
+        assert!(!store.can_redo());
+        assert!(!store.redo());
+        assert_eq!(fetch_starts(&store), vec![0.0, 2.0]);
+    }
+
+    #[test]
+    fn undo_counts_only_undone_strokes() {
+        let mut store = StrokeStore::default();
+        draw_stroke(&mut store, 0.0, 0);
+        draw_stroke(&mut store, 1.0, 100);
+        store.pen_down(
+            Vec2::new(2.0, 0.0),
+            PenDevice::Touch,
+            Duration::from_millis(200),
+        );
+        assert!(!store.undo());
+        store.pen_up();
+        let undo_count = (0..5).filter(|_| store.undo()).count();
+        assert_eq!(undo_count, 3);
+        assert!(store.strokes().is_empty());
+    }
+}
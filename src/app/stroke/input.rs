@@ -0,0 +1,130 @@
+use crate::app::{settings, stroke::*};
+use bevy_persistent::prelude::*;
+
+const GAMEPAD_PEN_SPEED: f32 = 480.0;
+const GAMEPAD_DEAD_ZONE: f32 = 0.1;
+const KEYBOARD_PEN_SPEED: f32 = 360.0;
+
+pub fn move_cursor_by_gamepad(
+    canvas_query: Query<&StrokeCanvas>,
+    mut cursor: ResMut<AppCursorData>,
+    gamepads: Res<Gamepads>,
+    axes: Res<Axis<GamepadAxis>>,
+    settings: Res<Persistent<settings::Settings>>,
+    window: Query<&Window>,
+    time: Res<Time>,
+) {
+    if !canvas_query.iter().any(|canvas| canvas.is_enabled) {
+        return;
+    }
+    for gamepad in gamepads.iter() {
+        let x = axes
+            .get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickX))
+            .unwrap_or(0.0);
+        let y = axes
+            .get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickY))
+            .unwrap_or(0.0);
+        let stick = Vec2::new(x, y);
+        if stick.length() > GAMEPAD_DEAD_ZONE {
+            let moving_ratio = settings.get_value("sensitivity") as f32 / 50.0;
+            let canvas_pos =
+                cursor.canvas_pos + stick * GAMEPAD_PEN_SPEED * moving_ratio * time.delta_seconds();
+            cursor.set_canvas_pos(canvas_pos, window.single());
+        }
+    }
+}
+
+pub fn move_cursor_by_keyboard(
+    canvas_query: Query<&StrokeCanvas>,
+    mut cursor: ResMut<AppCursorData>,
+    key_input: Res<Input<KeyCode>>,
+    settings: Res<Persistent<settings::Settings>>,
+    window: Query<&Window>,
+    time: Res<Time>,
+) {
+    if !canvas_query.iter().any(|canvas| canvas.is_enabled) {
+        return;
+    }
+    let mut direction = Vec2::ZERO;
+    if key_input.any_pressed([KeyCode::Up, KeyCode::W]) {
+        direction.y += 1.0;
+    }
+    if key_input.any_pressed([KeyCode::Down, KeyCode::S]) {
+        direction.y -= 1.0;
+    }
+    if key_input.any_pressed([KeyCode::Left, KeyCode::A]) {
+        direction.x -= 1.0;
+    }
+    if key_input.any_pressed([KeyCode::Right, KeyCode::D]) {
+        direction.x += 1.0;
+    }
+    if direction != Vec2::ZERO {
+        let moving_ratio = settings.get_value("sensitivity") as f32 / 50.0;
+        let canvas_pos = cursor.canvas_pos
+            + direction.normalize() * KEYBOARD_PEN_SPEED * moving_ratio * time.delta_seconds();
+        cursor.set_canvas_pos(canvas_pos, window.single());
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn handle_pen_input(
+    mut store: ResMut<StrokeStore>,
+    mut events: EventWriter<StrokeEvent>,
+    canvas_query: Query<(&GlobalTransform, &StrokeCanvas)>,
+    mut cursor: ResMut<AppCursorData>,
+    mouse_input: Res<Input<MouseButton>>,
+    key_input: Res<Input<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_input: Res<Input<GamepadButton>>,
+    touches: Res<Touches>,
+    window: Query<&Window>,
+    time: Res<Time>,
+) {
+    let (canvas_trans, canvas) = match canvas_query.get_single() {
+        Ok(canvas) => canvas,
+        Err(_) => return,
+    };
+    if !canvas.is_enabled {
+        if let Some(index) = store.pen_up() {
+            events.send(StrokeEvent::Ended { index });
+        }
+        return;
+    }
+    if let Some(touch_pos) = touches.first_pressed_position() {
+        cursor.set_window_pos(touch_pos, window.single());
+    }
+    let gamepad_buttons: Vec<GamepadButton> = gamepads
+        .iter()
+        .map(|gamepad| GamepadButton::new(gamepad, GamepadButtonType::RightTrigger2))
+        .collect();
+    let pen_down_device = if mouse_input.just_pressed(MouseButton::Left) {
+        Some(PenDevice::Mouse)
+    } else if touches.any_just_pressed() {
+        Some(PenDevice::Touch)
+    } else if key_input.just_pressed(KeyCode::Space) {
+        Some(PenDevice::Keyboard)
+    } else if gamepad_input.any_just_pressed(gamepad_buttons.clone()) {
+        Some(PenDevice::Gamepad)
+    } else {
+        None
+    };
+    let is_pen_held = mouse_input.pressed(MouseButton::Left)
+        || touches.iter().next().is_some()
+        || key_input.pressed(KeyCode::Space)
+        || gamepad_input.any_pressed(gamepad_buttons);
+    let local_pos = to_local_pos(canvas_trans, cursor.canvas_pos);
+    if store.is_drawing() {
+        if !is_pen_held {
+            if let Some(index) = store.pen_up() {
+                events.send(StrokeEvent::Ended { index });
+            }
+        } else if canvas.contains(local_pos) {
+            store.pen_move(local_pos, time.elapsed());
+        }
+    } else if let Some(device) = pen_down_device {
+        if canvas.contains(local_pos) {
+            let index = store.pen_down(local_pos, device, time.elapsed());
+            events.send(StrokeEvent::Started { index });
+        }
+    }
+}
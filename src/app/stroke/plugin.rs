@@ -0,0 +1,20 @@
+use crate::app::stroke::*;
+
+pub struct StrokePlugin;
+
+impl Plugin for StrokePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(StrokeStore::default())
+            .add_event::<StrokeEvent>()
+            .add_systems(
+                Update,
+                (
+                    input::move_cursor_by_gamepad,
+                    input::move_cursor_by_keyboard,
+                    input::handle_pen_input,
+                    render::draw_strokes,
+                )
+                    .chain(),
+            );
+    }
+}
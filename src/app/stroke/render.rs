@@ -0,0 +1,81 @@
+use crate::app::stroke::*;
+use bevy::utils::HashMap;
+
+const STROKE_Z_INDEX: f32 = 0.1;
+
+pub fn build_line_stroke(color: Color, line_width: f32) -> Stroke {
+    Stroke {
+        options: StrokeOptions::default()
+            .with_line_width(line_width)
+            .with_line_cap(LineCap::Round)
+            .with_line_join(LineJoin::Round),
+        color,
+    }
+}
+
+pub fn build_polyline_path(polylines: &[Vec<Vec2>]) -> Path {
+    let mut path_builder = PathBuilder::new();
+    for polyline in polylines.iter() {
+        if let Some(first) = polyline.first() {
+            path_builder.move_to(*first);
+            if polyline.len() == 1 {
+                path_builder.line_to(*first);
+            }
+            for pos in polyline.iter().skip(1) {
+                path_builder.line_to(*pos);
+            }
+        }
+    }
+    path_builder.build()
+}
+
+pub fn draw_strokes(
+    mut commands: Commands,
+    store: Res<StrokeStore>,
+    canvas_query: Query<Entity, With<StrokeCanvas>>,
+    mut shape_query: Query<(Entity, &StrokeShape, &mut Path)>,
+) {
+    if !store.is_changed() {
+        return;
+    }
+    let canvas_entity = match canvas_query.get_single() {
+        Ok(entity) => entity,
+        Err(_) => return,
+    };
+    let strokes = store.strokes();
+    let mut drawn_shapes: HashMap<usize, Entity> = HashMap::new();
+    for (entity, shape, mut path) in shape_query.iter_mut() {
+        if shape.0 >= strokes.len() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        if store.is_drawing() && shape.0 == strokes.len() - 1 {
+            *path = build_polyline_path(&[strokes[shape.0].points()]);
+        }
+        drawn_shapes.insert(shape.0, entity);
+    }
+    for (index, stroke) in strokes.iter().enumerate() {
+        if drawn_shapes.contains_key(&index) {
+            continue;
+        }
+        let shape_entity = commands
+            .spawn((
+                ShapeBundle {
+                    path: build_polyline_path(&[stroke.points()]),
+                    spatial: SpatialBundle {
+                        transform: Transform::from_xyz(
+                            0.0,
+                            0.0,
+                            STROKE_Z_INDEX + index as f32 * 0.0001,
+                        ),
+                        ..default()
+                    },
+                    ..default()
+                },
+                build_line_stroke(PEN_COLOR, PEN_LINE_W),
+                StrokeShape(index),
+            ))
+            .id();
+        commands.entity(canvas_entity).add_child(shape_entity);
+    }
+}
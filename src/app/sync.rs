@@ -0,0 +1,365 @@
+//! Optional leaderboard sync with a self-hosted HTTP server.
+//!
+//! Sync stays off until it is turned on and a sync URL is entered on the data settings page
+//! (`sync_enabled` and `sync_url` in `settings.json`). `ureq` is built without TLS, so only
+//! plain `http://` URLs are supported. All requests are JSON against `{sync_url}/records`:
+//!
+//! - `POST {sync_url}/records` sends one record as
+//!   `{"version": 1, "record": LeaderboardRecord}`. Any 2xx response acknowledges it. The server
+//!   must answer 2xx for a record it already has, matching records by `started_at` (the record
+//!   uid). Any other response or a connection error keeps the record queued for the next retry.
+//! - `GET {sync_url}/records` returns the merged records of every player as
+//!   `{"version": 1, "records": [LeaderboardRecord]}`. The client keeps the records that rank in
+//!   any list for the "Global" leaderboard tab.
+//!
+//! `LeaderboardRecord` is sent with the field names of the Rust struct: `player_name`, `time`,
+//! `score`, `match_point`, `undo_count`, `repaint_count`, `max_alpha_count`, `max_control_chain`,
+//! `max_hyper_chain`, `started_at`, `ended_at` and `seed` (the plant seed, `null` for records
+//! saved before it was kept). A stand-in server only needs to store the posted records by
+//! `started_at` and echo them back on `GET`.
+
+use crate::app::{
+    self,
+    leaderboard::{Leaderboard, LeaderboardRecord},
+    settings::Settings,
+};
+use bevy::{
+    prelude::*,
+    tasks::{block_on, IoTaskPool, Task},
+};
+use bevy_persistent::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{path::Path, time::Duration};
+
+pub const SYNC_PROTOCOL_VERSION: u32 = 1;
+const SYNC_INTERVAL_SECS: f32 = 30.0;
+const SYNC_TIMEOUT_SECS: u64 = 5;
+
+#[derive(Resource, Serialize, Deserialize)]
+pub struct SyncQueue {
+    records: Vec<LeaderboardRecord>,
+}
+
+impl SyncQueue {
+    pub fn push(&mut self, record: LeaderboardRecord) {
+        if !self
+            .records
+            .iter()
+            .any(|queued| queued.uid() == record.uid())
+        {
+            self.records.push(record);
+        }
+    }
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+    fn remove_sent(&mut self, sent_uids: &[String]) {
+        self.records
+            .retain(|record| !sent_uids.iter().any(|uid| uid == record.uid()));
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct GlobalLeaderboard {
+    pub leaderboard: Option<Leaderboard>,
+    pub last_error: Option<String>,
+}
+
+#[derive(Resource)]
+pub struct SyncTimer(Timer);
+
+impl SyncTimer {
+    pub fn request(&mut self) {
+        let duration = self.0.duration();
+        self.0
+            .set_elapsed(duration.saturating_sub(Duration::from_millis(1)));
+    }
+}
+
+#[derive(Component)]
+struct SyncTask(Task<SyncResult>);
+
+struct SyncResult {
+    sent_uids: Vec<String>,
+    records: Result<Vec<LeaderboardRecord>, String>,
+}
+
+#[derive(Serialize)]
+struct PostRecordBody<'a> {
+    version: u32,
+    record: &'a LeaderboardRecord,
+}
+
+#[derive(Deserialize)]
+struct RecordsBody {
+    records: Vec<LeaderboardRecord>,
+}
+
+pub fn push_record(
+    queue: &mut Persistent<SyncQueue>,
+    settings: &Settings,
+    record: &LeaderboardRecord,
+) {
+    if settings.is_enabled("sync") {
+        queue
+            .update(|queue| {
+                queue.push(record.clone());
+            })
+            .expect("failed to queue sync record");
+    }
+}
+
+fn handle_sync_timer(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut timer: ResMut<SyncTimer>,
+    settings: Res<Persistent<Settings>>,
+    queue: Res<Persistent<SyncQueue>>,
+    task_query: Query<&SyncTask>,
+) {
+    if !timer.0.tick(time.delta()).just_finished() {
+        return;
+    }
+    let url = settings.fetch_sync_url().trim_end_matches('/').to_string();
+    if !settings.is_enabled("sync") || url.is_empty() || !task_query.is_empty() {
+        return;
+    }
+    let records = queue.records.clone();
+    let task = IoTaskPool::get().spawn(async move { run_sync(url, records) });
+    commands.spawn(SyncTask(task));
+}
+
+fn handle_sync_task(
+    mut commands: Commands,
+    mut task_query: Query<(Entity, &mut SyncTask)>,
+    mut queue: ResMut<Persistent<SyncQueue>>,
+    mut global: ResMut<GlobalLeaderboard>,
+) {
+    for (entity, mut task) in task_query.iter_mut() {
+        if task.0.is_finished() {
+            let result = block_on(&mut task.0);
+            if !result.sent_uids.is_empty() {
+                queue
+                    .update(|queue| {
+                        queue.remove_sent(&result.sent_uids);
+                    })
+                    .expect("failed to update sync queue");
+            }
+            match result.records {
+                Ok(records) => {
+                    global.leaderboard = Some(Leaderboard::from_records(records));
+                    global.last_error = None;
+                }
+                Err(error) => global.last_error = Some(error),
+            }
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn run_sync(url: String, records: Vec<LeaderboardRecord>) -> SyncResult {
+    let agent = ureq::AgentBuilder::new()
+        .timeout(Duration::from_secs(SYNC_TIMEOUT_SECS))
+        .build();
+    let endpoint = format!("{}/records", url);
+    let mut sent_uids = vec![];
+    for record in records.iter() {
+        let body = PostRecordBody {
+            version: SYNC_PROTOCOL_VERSION,
+            record,
+        };
+        let result = serde_json::to_string(&body)
+            .map_err(|error| error.to_string())
+            .and_then(|body| {
+                agent
+                    .post(&endpoint)
+                    .set("Content-Type", "application/json")
+                    .send_string(&body)
+                    .map_err(|error| error.to_string())
+            });
+        if let Err(error) = result {
+            return SyncResult {
+                sent_uids,
+                records: Err(error),
+            };
+        }
+        sent_uids.push(record.uid().to_string());
+    }
+    let records = agent
+        .get(&endpoint)
+        .call()
+        .map_err(|error| error.to_string())
+        .and_then(|response| response.into_string().map_err(|error| error.to_string()))
+        .and_then(|body| {
+            serde_json::from_str::<RecordsBody>(&body).map_err(|error| error.to_string())
+        })
+        .map(|body| body.records);
+    SyncResult { sent_uids, records }
+}
+
+pub struct SyncPlugin;
+
+impl Plugin for SyncPlugin {
+    fn build(&self, app: &mut App) {
+        let config_dir = dirs::config_dir()
+            .map(|native_config_dir| native_config_dir.join(app::APP_CODE))
+            .unwrap_or(Path::new("local").join("configuration"));
+        let mut timer = SyncTimer(Timer::from_seconds(
+            SYNC_INTERVAL_SECS,
+            TimerMode::Repeating,
+        ));
+        timer.request();
+        app.insert_resource(
+            Persistent::<SyncQueue>::builder()
+                .name("sync queue")
+                .format(StorageFormat::Json)
+                .path(config_dir.join("sync_queue.json"))
+                .default(SyncQueue { records: vec![] })
+                .revertible(true)
+                .revert_to_default_on_deserialization_errors(true)
+                .build()
+                .expect("failed to initialize variables"),
+        )
+        .insert_resource(GlobalLeaderboard::default())
+        .insert_resource(timer)
+        .add_systems(Update, (handle_sync_timer, handle_sync_task));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::{TcpListener, TcpStream},
+        sync::{Arc, Mutex},
+        thread,
+    };
+
+    fn build_record(started_at: &str, score: u32) -> LeaderboardRecord {
+        LeaderboardRecord {
+            player_name: String::from("tester"),
+            score,
+            started_at: String::from(started_at),
+            ended_at: String::from(started_at),
+            ..default()
+        }
+    }
+
+    fn spawn_server(is_accepting: bool) -> (String, Arc<Mutex<Vec<LeaderboardRecord>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let stored = Arc::new(Mutex::new(vec![]));
+        let server_stored = stored.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                handle_request(stream, &server_stored, is_accepting);
+            }
+        });
+        (url, stored)
+    }
+
+    fn handle_request(
+        mut stream: TcpStream,
+        stored: &Mutex<Vec<LeaderboardRecord>>,
+        is_accepting: bool,
+    ) {
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line).unwrap();
+        let mut content_length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line.trim().is_empty() {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse().unwrap();
+                }
+            }
+        }
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).unwrap();
+        let (status, response) = if !is_accepting {
+            ("500 Internal Server Error", String::new())
+        } else if request_line.starts_with("POST /records") {
+            let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            let record: LeaderboardRecord =
+                serde_json::from_value(value["record"].clone()).unwrap();
+            let mut stored = stored.lock().unwrap();
+            if !stored.iter().any(|stored| stored.uid() == record.uid()) {
+                stored.push(record);
+            }
+            ("200 OK", String::from("{}"))
+        } else {
+            let records = stored.lock().unwrap().clone();
+            let body = serde_json::json!({
+                "version": SYNC_PROTOCOL_VERSION,
+                "records": records,
+            });
+            ("200 OK", body.to_string())
+        };
+        write!(
+            stream,
+            "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            response.len(),
+            response
+        )
+        .unwrap();
+    }
+
+    fn fetch_offline_url() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        format!("http://{}", listener.local_addr().unwrap())
+    }
+
+    #[test]
+    fn push_and_pull_records() {
+        let (url, stored) = spawn_server(true);
+        stored
+            .lock()
+            .unwrap()
+            .push(build_record("2024-01-01_00:00:00.000000000", 50));
+        let record = build_record("2024-01-02_00:00:00.000000000", 80);
+        let result = run_sync(url, vec![record.clone()]);
+        assert_eq!(result.sent_uids, vec![record.uid().to_string()]);
+        let records = result.records.unwrap();
+        assert_eq!(records.len(), 2);
+        assert!(records.iter().any(|pulled| pulled.uid() == record.uid()));
+        assert_eq!(stored.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn keep_records_queued_while_offline() {
+        let record = build_record("2024-01-02_00:00:00.000000000", 80);
+        let mut queue = SyncQueue { records: vec![] };
+        queue.push(record.clone());
+        queue.push(record.clone());
+        assert_eq!(queue.len(), 1);
+
+        let result = run_sync(fetch_offline_url(), queue.records.clone());
+        assert!(result.sent_uids.is_empty());
+        assert!(result.records.is_err());
+        queue.remove_sent(&result.sent_uids);
+        assert_eq!(queue.len(), 1);
+
+        let (url, _) = spawn_server(false);
+        let result = run_sync(url, queue.records.clone());
+        assert!(result.sent_uids.is_empty());
+        assert!(result.records.is_err());
+        queue.remove_sent(&result.sent_uids);
+        assert_eq!(queue.len(), 1);
+
+        let (url, stored) = spawn_server(true);
+        let result = run_sync(url, queue.records.clone());
+        queue.remove_sent(&result.sent_uids);
+        assert!(queue.is_empty());
+        assert_eq!(stored.lock().unwrap().len(), 1);
+    }
+}
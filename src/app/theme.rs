@@ -4,12 +4,14 @@ pub const BG_COLOR: Color = Color::rgb(0.875, 0.902, 0.878);
 pub const FG_COLOR: Color = Color::rgb(0.349, 0.431, 0.278);
 pub const SECONDARY_COLOR: Color = Color::rgb(0.608, 0.631, 0.373);
 pub const MUTE_COLOR: Color = Color::rgba(0.608, 0.631, 0.373, 0.8);
+pub const TARGET_COLOR: Color = Color::rgba(0.608, 0.631, 0.373, 0.4);
 pub const COVER_COLOR: Color = Color::rgba(0.0, 0.0, 0.0, 0.0);
 pub const BTN_BG: Color = Color::rgba(0.0, 0.0, 0.0, 0.0);
 pub const BTN_HOVERED_BG: Color = SECONDARY_COLOR;
 pub const BTN_PRESSED_BG: Color = SECONDARY_COLOR;
 pub const LINK_BG: Color = Color::rgba(0.0, 0.0, 0.0, 0.0);
 pub const CONTROL_COLOR: Color = Color::rgb(0.463, 0.529, 0.671);
+pub const ALERT_COLOR: Color = Color::rgb(0.702, 0.408, 0.365);
 
 pub const FONT: &str = "fonts/main.otf";
 pub const FONT_TITLE: &str = "fonts/title.otf";
@@ -1,10 +1,47 @@
-use bevy::prelude::*;
+use crate::app::status::AppStatus;
+use bevy::{prelude::*, utils::HashSet, window::WindowFocused};
 
 const APP_FRAME_SECS: f32 = 0.01;
 
 #[derive(Resource)]
 pub struct AppTimer(pub Timer);
 
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum ClockPause {
+    Menu,
+    Unfocused,
+    Effect,
+}
+
+#[derive(Resource, Default, Debug)]
+pub struct RoundClock {
+    is_running: bool,
+    pauses: HashSet<ClockPause>,
+}
+
+impl RoundClock {
+    pub fn start(&mut self) {
+        self.is_running = true;
+        self.pauses.retain(|pause| *pause == ClockPause::Unfocused);
+    }
+    pub fn stop(&mut self) {
+        self.is_running = false;
+    }
+    pub fn set_paused(&mut self, pause: ClockPause, is_paused: bool) {
+        if is_paused {
+            self.pauses.insert(pause);
+        } else {
+            self.pauses.remove(&pause);
+        }
+    }
+    pub fn is_paused(&self) -> bool {
+        !self.pauses.is_empty()
+    }
+    pub fn is_ticking(&self) -> bool {
+        self.is_running && !self.is_paused()
+    }
+}
+
 pub struct TimerPlugin;
 
 impl Plugin for TimerPlugin {
@@ -12,6 +49,29 @@ impl Plugin for TimerPlugin {
         app.insert_resource(AppTimer(Timer::from_seconds(
             APP_FRAME_SECS,
             TimerMode::Repeating,
-        )));
+        )))
+        .insert_resource(RoundClock::default())
+        .add_systems(Update, (handle_window_focus, tick_round_clock).chain());
+    }
+}
+
+fn handle_window_focus(
+    mut focused_events: EventReader<WindowFocused>,
+    mut clock: ResMut<RoundClock>,
+) {
+    for event in focused_events.read() {
+        clock.set_paused(ClockPause::Unfocused, !event.focused);
+    }
+}
+
+fn tick_round_clock(
+    mut timer: ResMut<AppTimer>,
+    clock: Res<RoundClock>,
+    mut status: ResMut<AppStatus>,
+    time: Res<Time>,
+) {
+    timer.0.tick(time.delta());
+    if clock.is_ticking() {
+        status.time += timer.0.times_finished_this_tick();
     }
 }
@@ -10,10 +10,13 @@ pub enum PageState {
     #[default]
     Menu,
     Game,
+    Leaderboard,
+    Achievement,
     Settings,
     SettingsAudio,
     SettingsControl,
     SettingsDisplay,
+    SettingsData,
     AboutMain,
     AboutAudio,
     AboutVisual,
@@ -21,9 +24,11 @@ pub enum PageState {
     Dev,
 }
 
-pub const PAGES: [&dyn page::PageBase; 10] = [
+pub const PAGES: [&dyn page::PageBase; 13] = [
     &page::menu::Page,
     &page::game::Page,
+    &page::leaderboard::Page,
+    &page::achievement::Page,
     &page::help::Page,
     &page::about::main::Page,
     &page::about::audio::Page,
@@ -32,4 +37,5 @@ pub const PAGES: [&dyn page::PageBase; 10] = [
     &page::settings::audio::Page,
     &page::settings::control::Page,
     &page::settings::display::Page,
+    &page::settings::data::Page,
 ];
@@ -1,9 +1,11 @@
 use crate::{app, app::theme::*, app::ui, book::*};
 
 pub mod about;
+pub mod achievement;
 pub mod dev;
 pub mod game;
 pub mod help;
+pub mod leaderboard;
 pub mod menu;
 pub mod settings;
 
@@ -0,0 +1,375 @@
+use crate::{
+    app::{
+        achievement::{self, AchievementUiStatus},
+        anime_effect, integrity, interaction,
+        theme::*,
+        ui,
+    },
+    book::page::*,
+};
+use bevy_persistent::prelude::*;
+use bevy_ui_navigation::{prelude::*, NavRequestSystem};
+
+const PAGE_CODE: &str = "achievement";
+const PAGE_NAME: &str = "Achievements";
+const PAGE_ICON: &str = "star";
+
+pub struct Page;
+
+impl PageBase for Page {
+    fn code(&self) -> &str {
+        PAGE_CODE
+    }
+    fn name(&self) -> &str {
+        PAGE_NAME
+    }
+    fn icon(&self) -> &str {
+        PAGE_ICON
+    }
+    fn state(&self) -> PageState {
+        PageState::Achievement
+    }
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(self.state()),
+            (interaction::reset_default_focus, page_enter),
+        )
+        .add_systems(
+            Update,
+            (
+                handle_ui_navigation,
+                update_achievement_status,
+                interaction::handle_default_focus,
+            )
+                .after(NavRequestSystem)
+                .run_if(in_state(self.state())),
+        )
+        .add_systems(
+            OnExit(self.state()),
+            (anime_effect::clear_anime_effect, ui::despawn_ui::<OnPage>),
+        );
+    }
+}
+
+#[derive(Component)]
+struct OnPage;
+
+#[derive(Component)]
+enum ButtonAction {
+    MoveToPage(PageState),
+    TogglePin(String),
+}
+
+#[derive(Component)]
+struct StatusIcon(String);
+
+#[derive(Component)]
+struct PinnedCountText;
+
+const ROW_W: f32 = 165.0;
+const ROW_PADDING: f32 = 2.0;
+const BADGE_RATIO: f32 = 1.8;
+const DETAIL_FS_RATIO: f32 = 0.55;
+const LOCKED_BADGE_PATH: &str = "images/achievement/locked.png";
+
+fn page_enter(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    ach_store: Res<Persistent<achievement::AchievementStore>>,
+    report: Res<integrity::IntegrityReport>,
+) {
+    let has_flagged =
+        achievement::achievements().any(|ach_def| report.is_achievement_flagged(ach_def.code()));
+    commands
+        .spawn((build_page_layout(), OnPage))
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        width: Val::Percent(100.0),
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::SpaceBetween,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    build_game_title(parent, &asset_server);
+                    build_page_title(parent, &asset_server, PAGE_NAME, PAGE_ICON);
+                    parent
+                        .spawn(NodeBundle {
+                            style: Style {
+                                flex_grow: 1.0,
+                                flex_direction: FlexDirection::Column,
+                                align_items: AlignItems::Center,
+                                justify_content: JustifyContent::Center,
+                                row_gap: ui::px_p(4.0),
+                                ..default()
+                            },
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            parent
+                                .spawn(NodeBundle {
+                                    style: Style {
+                                        display: Display::Grid,
+                                        grid_template_columns: RepeatedGridTrack::auto(2),
+                                        column_gap: ui::px_p(3.0),
+                                        row_gap: ui::px_p(2.0),
+                                        margin: UiRect::top(ui::px_p(20.0)),
+                                        ..default()
+                                    },
+                                    ..default()
+                                })
+                                .with_children(|parent| {
+                                    for ach_def in achievement::achievements() {
+                                        build_achievement_row(
+                                            parent,
+                                            &asset_server,
+                                            &ach_store,
+                                            ach_def,
+                                            report.is_achievement_flagged(ach_def.code()),
+                                        );
+                                    }
+                                });
+                            parent.spawn((
+                                TextBundle::from_section(
+                                    "",
+                                    TextStyle {
+                                        font: asset_server.load(FONT),
+                                        font_size: ui::FONT_SIZE * 0.6,
+                                        color: MUTE_COLOR,
+                                    },
+                                ),
+                                PinnedCountText,
+                            ));
+                            if has_flagged {
+                                parent.spawn(TextBundle::from_section(
+                                    "Achievements in red failed verification",
+                                    TextStyle {
+                                        font: asset_server.load(FONT),
+                                        font_size: ui::FONT_SIZE * 0.6,
+                                        color: ALERT_COLOR,
+                                    },
+                                ));
+                            }
+                        });
+                });
+            ui::build_icon_btn(
+                parent,
+                &asset_server,
+                (
+                    ButtonAction::MoveToPage(PageState::Menu),
+                    app::interaction::IaButton,
+                    Focusable::default(),
+                    app::interaction::IaDefaultFocus,
+                ),
+                Style {
+                    position_type: PositionType::Absolute,
+                    bottom: ui::px_p(ui::PAGE_PADDING),
+                    left: ui::px_p(ui::PAGE_PADDING),
+                    ..default()
+                },
+                "arrow-left-bold_x1.5",
+            );
+        });
+}
+
+fn build_achievement_row(
+    parent: &mut ChildBuilder,
+    asset_server: &Res<AssetServer>,
+    ach_store: &achievement::AchievementStore,
+    ach_def: &dyn achievement::AchievementDefBase,
+    is_flagged: bool,
+) {
+    let record = ach_store.fetch_record(ach_def.code());
+    let tier = ach_store.fetch_tier(ach_def.code());
+    let next_tier = if record.is_done {
+        tier
+    } else {
+        achievement::next_tier(tier)
+    };
+    let is_unlocked = record.is_done || tier.is_some();
+    let name_color = if is_flagged {
+        ALERT_COLOR
+    } else if is_unlocked {
+        ach_def.color()
+    } else {
+        FG_COLOR
+    };
+    let detail_style = TextStyle {
+        font: asset_server.load(FONT),
+        font_size: ui::FONT_SIZE * DETAIL_FS_RATIO,
+        color: MUTE_COLOR,
+    };
+    parent
+        .spawn((
+            ButtonBundle {
+                style: Style {
+                    width: ui::px_p(ROW_W),
+                    align_items: AlignItems::Center,
+                    column_gap: ui::px_p(3.0),
+                    padding: UiRect::all(ui::px_p(ROW_PADDING)),
+                    border: UiRect::all(ui::px_p(0.5)),
+                    ..default()
+                },
+                background_color: BG_COLOR.into(),
+                border_color: MUTE_COLOR.into(),
+                ..default()
+            },
+            ButtonAction::TogglePin(ach_def.code().to_string()),
+            app::interaction::IaButton,
+            Focusable::default(),
+        ))
+        .with_children(|parent| {
+            let badge_path = if is_unlocked {
+                ach_def.icon_path()
+            } else {
+                String::from(LOCKED_BADGE_PATH)
+            };
+            parent.spawn(ImageBundle {
+                style: Style {
+                    width: Val::Px(ui::ICON_SIZE * BADGE_RATIO),
+                    height: Val::Px(ui::ICON_SIZE * BADGE_RATIO),
+                    ..default()
+                },
+                image: UiImage::new(asset_server.load(badge_path)),
+                ..default()
+            });
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_grow: 1.0,
+                        flex_direction: FlexDirection::Column,
+                        row_gap: ui::px_p(1.0),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        ach_def.tier_name(next_tier),
+                        TextStyle {
+                            font: asset_server.load(FONT),
+                            font_size: ui::FONT_SIZE * 0.8,
+                            color: name_color,
+                        },
+                    ));
+                    parent.spawn(TextBundle::from_section(
+                        ach_def.tier_description(next_tier),
+                        detail_style.clone(),
+                    ));
+                    if let Some((current, target)) = ach_def.progress(ach_store) {
+                        achievement::build_progress_ui(
+                            parent,
+                            asset_server,
+                            ach_def,
+                            current,
+                            target,
+                        );
+                    }
+                });
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::End,
+                        row_gap: ui::px_p(1.0),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn((
+                        ImageBundle {
+                            style: Style {
+                                width: Val::Px(ui::ICON_SIZE),
+                                height: Val::Px(ui::ICON_SIZE),
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        StatusIcon(ach_def.code().to_string()),
+                    ));
+                    if let Some(tier) = tier {
+                        parent.spawn(TextBundle::from_section(
+                            tier.name(),
+                            TextStyle {
+                                color: ach_def.color(),
+                                ..detail_style.clone()
+                            },
+                        ));
+                    }
+                    if !record.done_at.is_empty() {
+                        parent.spawn(TextBundle::from_section(
+                            format_date(&record.done_at),
+                            detail_style.clone(),
+                        ));
+                    }
+                });
+        });
+}
+
+fn format_date(datetime: &str) -> &str {
+    datetime.split('_').next().unwrap_or_default()
+}
+
+fn update_achievement_status(
+    asset_server: Res<AssetServer>,
+    ach_store: Res<Persistent<achievement::AchievementStore>>,
+    mut icon_query: Query<(&StatusIcon, &mut UiImage, &mut Visibility)>,
+    mut text_query: Query<&mut Text, With<PinnedCountText>>,
+    added_query: Query<Entity, Added<StatusIcon>>,
+) {
+    if !ach_store.is_changed() && added_query.is_empty() {
+        return;
+    }
+    for (status_icon, mut image, mut visibility) in icon_query.iter_mut() {
+        let icon = match ach_store.fetch_ui_status(&status_icon.0) {
+            AchievementUiStatus::Normal => None,
+            AchievementUiStatus::Pinned => Some("ach-push-pin"),
+            AchievementUiStatus::Done => Some("ach-check-circle"),
+        };
+        match icon {
+            Some(icon) => {
+                *image = UiImage::new(asset_server.load(format!("images/icons/{}.png", icon)));
+                *visibility = Visibility::Inherited;
+            }
+            None => *visibility = Visibility::Hidden,
+        }
+    }
+    let pinned_count = achievement::achievements()
+        .filter(|ach_def| ach_store.is_pinned(ach_def.code()))
+        .count();
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value = format!(
+            "Pinned {}/{}, pinned achievements are tracked first in the next round",
+            pinned_count,
+            achievement::MAX_RUNNING_ACH_COUNT
+        );
+    }
+}
+
+fn handle_ui_navigation(
+    mut actions: Query<&mut ButtonAction>,
+    mut events: EventReader<NavEvent>,
+    mut page_state: ResMut<NextState<PageState>>,
+    mut ach_store: ResMut<Persistent<achievement::AchievementStore>>,
+) {
+    events.nav_iter().activated_in_query_foreach_mut(
+        &mut actions,
+        |mut action| match &mut *action {
+            ButtonAction::MoveToPage(state) => page_state.set(*state),
+            ButtonAction::TogglePin(code) => {
+                if !ach_store.is_done(code) {
+                    ach_store
+                        .update(|store| {
+                            store.toggle_pin(code);
+                        })
+                        .expect("failed to update achievement pin");
+                }
+            }
+        },
+    );
+}
@@ -49,15 +49,6 @@ enum ButtonAction {
     MoveToPage(PageState),
 }
 
-#[derive(Component)]
-struct LeaderboardList(String);
-
-#[derive(Component)]
-struct ScreenshotPanel;
-
-#[derive(Component)]
-struct ScreenshotImage;
-
 const COLORS: [Color; 4] = [FG_COLOR, BG_COLOR, SECONDARY_COLOR, MUTE_COLOR];
 
 fn page_enter(mut commands: Commands, asset_server: Res<AssetServer>) {
@@ -223,7 +214,7 @@ fn page_enter(mut commands: Commands, asset_server: Res<AssetServer>) {
                                     ..default()
                                 })
                                 .with_children(|parent| {
-                                    for ach_def in app::achievement::ACHIEVEMENTS {
+                                    for ach_def in app::achievement::achievements() {
                                         parent
                                             .spawn(NodeBundle {
                                                 style: Style {
@@ -1,9 +1,47 @@
-use crate::{app::anime_effect, book::page::*};
+use crate::{
+    app::{
+        achievement, anime_effect, interaction, key_binding, plant, settings, shape_match, status,
+        stroke, theme::*, ui,
+    },
+    book::page::*,
+};
+use bevy::ecs::system::SystemParam;
+use bevy_persistent::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+use bevy_ui_navigation::{prelude::*, NavRequestSystem};
+use rand::{thread_rng, Rng};
+
+mod drawing;
+mod evaluating;
+mod ready;
+mod result;
 
 const PAGE_CODE: &str = "game";
 const PAGE_NAME: &str = "Start";
 const PAGE_ICON: &str = "play";
 
+#[derive(Clone, Copy, Default, Eq, PartialEq, Debug, Hash, States)]
+pub enum GamePhase {
+    #[default]
+    Inactive,
+    Ready,
+    Drawing,
+    Evaluating,
+    Result,
+}
+
+trait PhaseBase {
+    fn state(&self) -> GamePhase;
+    fn build(&self, app: &mut App);
+}
+
+const PHASES: [&dyn PhaseBase; 4] = [
+    &ready::Phase,
+    &drawing::Phase,
+    &evaluating::Phase,
+    &result::Phase,
+];
+
 pub struct Page;
 
 impl PageBase for Page {
@@ -20,14 +58,353 @@ impl PageBase for Page {
         PageState::Game
     }
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(self.state()), page_enter)
+        app.add_state::<GamePhase>()
+            .insert_resource(GameRound::default())
+            .insert_resource(ReplaySeed::default())
+            .add_systems(
+                OnEnter(self.state()),
+                (interaction::reset_default_focus, page_enter),
+            )
+            .add_systems(
+                Update,
+                (
+                    handle_ui_navigation,
+                    update_time_text,
+                    interaction::handle_default_focus,
+                )
+                    .after(NavRequestSystem)
+                    .run_if(in_state(self.state())),
+            )
             .add_systems(
                 OnExit(self.state()),
-                (anime_effect::clear_anime_effect, page_exit),
+                (
+                    anime_effect::clear_anime_effect,
+                    ui::despawn_ui::<OnPage>,
+                    page_exit,
+                ),
             );
+        for phase in PHASES {
+            phase.build(app);
+        }
     }
 }
 
-fn page_enter() {}
+#[derive(Component)]
+struct OnPage;
+
+#[derive(Component)]
+struct GameCanvas;
+
+#[derive(Component)]
+struct TargetShape;
+
+#[derive(Component)]
+struct TimeText;
+
+#[derive(Component)]
+enum RepaintAction {
+    Open,
+    Confirm,
+    Cancel,
+}
+
+#[derive(Component)]
+enum ButtonAction {
+    BackToMainMenu,
+    Undo,
+    Redo,
+    Finish,
+    Resume,
+    Retry,
+    SaveRecord,
+}
+
+#[derive(Resource, Default)]
+pub struct ReplaySeed(pub Option<u64>);
 
-fn page_exit() {}
+#[derive(Clone, Copy)]
+struct RoundConfig {
+    seed: u64,
+    metric: shape_match::MatchMetricKind,
+}
+
+impl RoundConfig {
+    fn from_settings(seed: u64, settings: &settings::Settings) -> Self {
+        Self {
+            seed,
+            metric: shape_match::fetch_metric_kind(settings.fetch_match_metric()),
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+struct GameRound {
+    plant: plant::Plant,
+    target: Vec<Vec<Vec2>>,
+    metric: shape_match::MatchMetricKind,
+}
+
+const ROUND_PLANT_DEPTH: u8 = 3;
+const ROUND_PLANT_BRANCH_ANGLE: f32 = 35.0;
+const ROUND_PLANT_LEAF_DENSITY: f32 = 0.6;
+const PLANT_MARGIN: f32 = 60.0;
+const CANVAS_POS: Vec2 = Vec2::new(0.0, -20.0);
+const CANVAS_SIZE: Vec2 = Vec2::new(600.0, 540.0);
+const CANVAS_Z_INDEX: f32 = 0.5;
+const CANVAS_BORDER_W: f32 = ui::SPACE_SIZE * 0.5;
+const TARGET_LINE_W: f32 = ui::SPACE_SIZE * 2.4;
+
+fn page_enter(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut key_binding: ResMut<key_binding::KeyBindingConfig>,
+    mut round_params: RoundParams,
+    mut next_phase: ResMut<NextState<GamePhase>>,
+    mut replay_seed: ResMut<ReplaySeed>,
+    settings: Res<Persistent<settings::Settings>>,
+) {
+    key_binding.mode = key_binding::KeyBindingMode::Keyboard;
+    let seed = replay_seed.0.take().unwrap_or_else(|| thread_rng().gen());
+    start_round(
+        &mut round_params,
+        RoundConfig::from_settings(seed, &settings),
+    );
+    commands
+        .spawn((build_page_layout(), OnPage))
+        .with_children(|parent| {
+            build_game_title(parent, &asset_server);
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        top: ui::px_p(ui::PAGE_PADDING),
+                        right: ui::px_p(ui::PAGE_PADDING),
+                        align_items: AlignItems::Center,
+                        column_gap: ui::px_p(3.0),
+                        padding: UiRect::all(ui::px_p(2.0)),
+                        ..default()
+                    },
+                    background_color: BG_COLOR.into(),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    let icon = asset_server.load("images/icons/timer-fill.png");
+                    parent.spawn(ImageBundle {
+                        style: Style {
+                            width: Val::Px(ui::ICON_SIZE),
+                            height: Val::Px(ui::ICON_SIZE),
+                            ..default()
+                        },
+                        image: UiImage::new(icon),
+                        ..default()
+                    });
+                    parent.spawn((
+                        TextBundle::from_section(
+                            format_time(0),
+                            TextStyle {
+                                font: asset_server.load(FONT),
+                                font_size: ui::FONT_SIZE,
+                                color: FG_COLOR,
+                            },
+                        ),
+                        TimeText,
+                    ));
+                });
+            ui::build_icon_btn(
+                parent,
+                &asset_server,
+                (
+                    ButtonAction::BackToMainMenu,
+                    app::interaction::IaButton,
+                    Focusable::default(),
+                ),
+                Style {
+                    position_type: PositionType::Absolute,
+                    bottom: ui::px_p(ui::PAGE_PADDING),
+                    left: ui::px_p(ui::PAGE_PADDING),
+                    ..default()
+                },
+                "arrow-left-bold_x1.5",
+            );
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        bottom: ui::px_p(ui::PAGE_PADDING),
+                        right: ui::px_p(ui::PAGE_PADDING),
+                        align_items: AlignItems::Center,
+                        column_gap: ui::px_p(4.0),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    ui::build_icon_btn(
+                        parent,
+                        &asset_server,
+                        (
+                            RepaintAction::Open,
+                            app::interaction::IaButton,
+                            Focusable::default(),
+                        ),
+                        Style::default(),
+                        "key_clear",
+                    );
+                    ui::build_icon_btn(
+                        parent,
+                        &asset_server,
+                        (
+                            ButtonAction::Undo,
+                            app::interaction::IaButton,
+                            Focusable::default(),
+                        ),
+                        Style::default(),
+                        "arrow-counter-clockwise",
+                    );
+                    ui::build_icon_btn(
+                        parent,
+                        &asset_server,
+                        (
+                            ButtonAction::Redo,
+                            app::interaction::IaButton,
+                            Focusable::default(),
+                        ),
+                        Style::default(),
+                        "arrow-clockwise",
+                    );
+                    ui::build_btn(
+                        parent,
+                        &asset_server,
+                        (
+                            ButtonAction::Finish,
+                            app::interaction::IaButton,
+                            Focusable::default(),
+                            app::interaction::IaDefaultFocus,
+                        ),
+                        Style {
+                            padding: UiRect::all(ui::px_p(ui::BTN_PADDING)),
+                            ..default()
+                        },
+                        Some("Done"),
+                        Some("ach-check-circle"),
+                    );
+                });
+        });
+    build_canvas(&mut commands, &round_params.round);
+    next_phase.set(GamePhase::Ready);
+}
+
+fn page_exit(
+    mut commands: Commands,
+    canvas_query: Query<Entity, With<GameCanvas>>,
+    mut key_binding: ResMut<key_binding::KeyBindingConfig>,
+    mut next_phase: ResMut<NextState<GamePhase>>,
+) {
+    key_binding.mode = key_binding::KeyBindingMode::Navgation;
+    next_phase.set(GamePhase::Inactive);
+    for entity in canvas_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+#[derive(SystemParam)]
+struct RoundParams<'w> {
+    status: ResMut<'w, status::AppStatus>,
+    round: ResMut<'w, GameRound>,
+    stroke_store: ResMut<'w, stroke::StrokeStore>,
+    ach_store: ResMut<'w, Persistent<achievement::AchievementStore>>,
+    ach_info: ResMut<'w, achievement::AchievementInfo>,
+    time: Res<'w, Time>,
+}
+
+fn start_round(round_params: &mut RoundParams, config: RoundConfig) {
+    let seed = config.seed;
+    round_params.status.reset();
+    round_params.status.seed = seed;
+    round_params.stroke_store.reset(round_params.time.elapsed());
+    round_params.ach_info.reset(&round_params.ach_store);
+    let plant = plant::Plant::generate(plant::PlantParam {
+        seed,
+        depth: ROUND_PLANT_DEPTH,
+        branch_angle: ROUND_PLANT_BRANCH_ANGLE,
+        leaf_density: ROUND_PLANT_LEAF_DENSITY,
+        ..default()
+    })
+    .fit(CANVAS_SIZE - PLANT_MARGIN * 2.0);
+    *round_params.round = GameRound {
+        target: plant.polylines(),
+        plant,
+        metric: config.metric,
+    };
+}
+
+fn build_canvas(commands: &mut Commands, round: &GameRound) {
+    commands
+        .spawn((
+            SpatialBundle {
+                transform: Transform::from_xyz(CANVAS_POS.x, CANVAS_POS.y, CANVAS_Z_INDEX),
+                ..default()
+            },
+            GameCanvas,
+            stroke::StrokeCanvas {
+                size: CANVAS_SIZE,
+                is_enabled: false,
+            },
+        ))
+        .with_children(|parent| {
+            let rect = shapes::Rectangle {
+                extents: CANVAS_SIZE,
+                origin: RectangleOrigin::Center,
+            };
+            parent.spawn((
+                ShapeBundle {
+                    path: GeometryBuilder::build_as(&rect),
+                    ..default()
+                },
+                Stroke::new(MUTE_COLOR, CANVAS_BORDER_W),
+            ));
+            parent.spawn((
+                ShapeBundle {
+                    path: round.plant.path(),
+                    spatial: SpatialBundle {
+                        transform: Transform::from_xyz(0.0, 0.0, 0.01),
+                        visibility: Visibility::Hidden,
+                        ..default()
+                    },
+                    ..default()
+                },
+                stroke::build_line_stroke(TARGET_COLOR, TARGET_LINE_W),
+                TargetShape,
+            ));
+        });
+}
+
+fn update_time_text(
+    status: Res<status::AppStatus>,
+    mut text_query: Query<&mut Text, With<TimeText>>,
+) {
+    if !status.is_changed() {
+        return;
+    }
+    if let Ok(mut text) = text_query.get_single_mut() {
+        text.sections[0].value = format_time(status.time);
+    }
+}
+
+fn format_time(time: u32) -> String {
+    format!("{:>3}.{:0>2}", time / 100, time % 100)
+}
+
+fn handle_ui_navigation(
+    mut actions: Query<&mut ButtonAction>,
+    mut events: EventReader<NavEvent>,
+    mut page_state: ResMut<NextState<PageState>>,
+) {
+    events
+        .nav_iter()
+        .activated_in_query_foreach_mut(&mut actions, |action| {
+            if let ButtonAction::BackToMainMenu = *action {
+                page_state.set(PageState::Menu);
+            }
+        });
+}
@@ -0,0 +1,597 @@
+use super::*;
+use crate::app::{audio, key_binding, settings, timer};
+use bevy_tweening::{lens::TransformScaleLens, *};
+use std::time::Duration;
+
+pub struct Phase;
+
+impl PhaseBase for Phase {
+    fn state(&self) -> GamePhase {
+        GamePhase::Drawing
+    }
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(self.state()), phase_enter)
+            .add_systems(
+                Update,
+                (
+                    handle_ui_navigation,
+                    handle_undo_redo,
+                    record_stroke_chain,
+                    handle_repaint_navigation,
+                    handle_repaint_key,
+                    handle_eraser_done,
+                    handle_pause_key,
+                    sync_round_clock,
+                    sync_key_binding,
+                    sync_finish_btn,
+                )
+                    .after(NavRequestSystem)
+                    .run_if(in_state(self.state())),
+            )
+            .add_systems(
+                OnExit(self.state()),
+                (ui::despawn_ui::<OnPhase>, phase_exit),
+            );
+    }
+}
+
+#[derive(Component)]
+struct OnPhase;
+
+#[derive(Component)]
+struct RepaintConfirm;
+
+#[derive(Component)]
+struct PausePanel;
+
+#[derive(Component)]
+struct Eraser;
+
+const ERASER_Z_INDEX: f32 = 0.5;
+const ERASER_DURATION_MS: u64 = 400;
+const ERASER_DONE_EVENT: u64 = 41;
+const HYPER_CHAIN_GAP_MS: u32 = 500;
+
+fn phase_enter(mut clock: ResMut<timer::RoundClock>) {
+    clock.start();
+}
+
+fn phase_exit(
+    mut clock: ResMut<timer::RoundClock>,
+    mut key_binding: ResMut<key_binding::KeyBindingConfig>,
+    mut canvas_query: Query<&mut stroke::StrokeCanvas>,
+) {
+    clock.stop();
+    if key_binding.mode == key_binding::KeyBindingMode::Drawing {
+        key_binding.mode = key_binding::KeyBindingMode::Keyboard;
+    }
+    for mut canvas in canvas_query.iter_mut() {
+        canvas.is_enabled = false;
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn handle_ui_navigation(
+    mut commands: Commands,
+    mut actions: Query<&mut ButtonAction>,
+    mut events: EventReader<NavEvent>,
+    mut stroke_store: ResMut<stroke::StrokeStore>,
+    mut status: ResMut<status::AppStatus>,
+    clock: Res<timer::RoundClock>,
+    pause_query: Query<Entity, With<PausePanel>>,
+    mut next_phase: ResMut<NextState<GamePhase>>,
+) {
+    events.nav_iter().activated_in_query_foreach_mut(
+        &mut actions,
+        |mut action| match &mut *action {
+            ButtonAction::Undo if clock.is_ticking() => {
+                undo_stroke(&mut stroke_store, &mut status);
+            }
+            ButtonAction::Redo if clock.is_ticking() => {
+                stroke_store.redo();
+            }
+            ButtonAction::Finish if clock.is_ticking() && !stroke_store.strokes().is_empty() => {
+                next_phase.set(GamePhase::Evaluating);
+            }
+            ButtonAction::Resume => {
+                for entity in pause_query.iter() {
+                    commands.entity(entity).despawn_recursive();
+                }
+            }
+            _ => (),
+        },
+    );
+}
+
+fn sync_finish_btn(
+    stroke_store: Res<stroke::StrokeStore>,
+    btn_query: Query<(&ButtonAction, &Children)>,
+    mut text_query: Query<&mut Text>,
+    mut image_query: Query<&mut BackgroundColor, With<UiImage>>,
+) {
+    if !stroke_store.is_changed() {
+        return;
+    }
+    let color = if stroke_store.strokes().is_empty() {
+        MUTE_COLOR
+    } else {
+        FG_COLOR
+    };
+    for (action, children) in btn_query.iter() {
+        if !matches!(action, ButtonAction::Finish) {
+            continue;
+        }
+        for child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(*child) {
+                text.sections[0].style.color = color;
+            }
+            if let Ok(mut bg_color) = image_query.get_mut(*child) {
+                *bg_color = color.into();
+            }
+        }
+    }
+}
+
+type ClockMenu = Or<(With<RepaintConfirm>, With<PausePanel>)>;
+
+fn sync_round_clock(
+    mut clock: ResMut<timer::RoundClock>,
+    effect_query: Query<(), With<Eraser>>,
+    menu_query: Query<(), ClockMenu>,
+    mut canvas_query: Query<&mut stroke::StrokeCanvas>,
+) {
+    clock.set_paused(timer::ClockPause::Effect, !effect_query.is_empty());
+    clock.set_paused(timer::ClockPause::Menu, !menu_query.is_empty());
+    let is_enabled = clock.is_ticking();
+    for mut canvas in canvas_query.iter_mut() {
+        if canvas.is_enabled != is_enabled {
+            canvas.is_enabled = is_enabled;
+        }
+    }
+}
+
+fn sync_key_binding(
+    menu_query: Query<(), ClockMenu>,
+    mut key_binding: ResMut<key_binding::KeyBindingConfig>,
+) {
+    let mode = if menu_query.is_empty() {
+        key_binding::KeyBindingMode::Drawing
+    } else {
+        key_binding::KeyBindingMode::Keyboard
+    };
+    // NOTE: the page resets the binding to navigation on exit, keep it
+    if key_binding.mode != mode && key_binding.mode != key_binding::KeyBindingMode::Navgation {
+        key_binding.mode = mode;
+    }
+}
+
+fn handle_pause_key(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    key_input: Res<Input<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_input: Res<Input<GamepadButton>>,
+    pause_query: Query<Entity, With<PausePanel>>,
+    confirm_query: Query<Entity, With<RepaintConfirm>>,
+) {
+    if !confirm_query.is_empty() {
+        return;
+    }
+    let mut is_pause = key_input.just_pressed(KeyCode::Escape);
+    for gamepad in gamepads.iter() {
+        if gamepad_input.just_pressed(GamepadButton::new(gamepad, GamepadButtonType::Start)) {
+            is_pause = true;
+        }
+    }
+    if !is_pause {
+        return;
+    }
+    if pause_query.is_empty() {
+        open_pause_panel(&mut commands, &asset_server);
+    } else {
+        for entity in pause_query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+fn open_pause_panel(commands: &mut Commands, asset_server: &Res<AssetServer>) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                ..default()
+            },
+            PausePanel,
+            OnPhase,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: ui::px_p(3.0),
+                        padding: UiRect::all(ui::px_p(6.0)),
+                        border: UiRect::all(ui::px_p(0.5)),
+                        ..default()
+                    },
+                    background_color: BG_COLOR.into(),
+                    border_color: MUTE_COLOR.into(),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        "Paused",
+                        TextStyle {
+                            font: asset_server.load(FONT),
+                            font_size: ui::FONT_SIZE,
+                            color: FG_COLOR,
+                        },
+                    ));
+                    parent
+                        .spawn(NodeBundle {
+                            style: Style {
+                                align_items: AlignItems::Center,
+                                column_gap: ui::px_p(4.0),
+                                ..default()
+                            },
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            ui::build_btn(
+                                parent,
+                                asset_server,
+                                (
+                                    ButtonAction::BackToMainMenu,
+                                    app::interaction::IaButton,
+                                    Focusable::default(),
+                                ),
+                                Style {
+                                    padding: UiRect::all(ui::px_p(ui::BTN_PADDING)),
+                                    ..default()
+                                },
+                                Some("Menu"),
+                                Some("arrow-left"),
+                            );
+                            ui::build_btn(
+                                parent,
+                                asset_server,
+                                (
+                                    ButtonAction::Resume,
+                                    app::interaction::IaButton,
+                                    Focusable::default(),
+                                ),
+                                Style {
+                                    padding: UiRect::all(ui::px_p(ui::BTN_PADDING)),
+                                    ..default()
+                                },
+                                Some("Resume"),
+                                Some("play"),
+                            );
+                        });
+                });
+        });
+}
+
+fn handle_undo_redo(
+    mut stroke_store: ResMut<stroke::StrokeStore>,
+    mut status: ResMut<status::AppStatus>,
+    canvas_query: Query<&stroke::StrokeCanvas>,
+    key_input: Res<Input<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_input: Res<Input<GamepadButton>>,
+) {
+    if !canvas_query.iter().all(|canvas| canvas.is_enabled) {
+        return;
+    }
+    let is_ctrl_on = key_input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+    let is_shift_on = key_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    let mut is_undo = key_input.just_pressed(KeyCode::Back)
+        || (is_ctrl_on && !is_shift_on && key_input.just_pressed(KeyCode::Z));
+    let mut is_redo = (is_ctrl_on && key_input.just_pressed(KeyCode::Y))
+        || (is_ctrl_on && is_shift_on && key_input.just_pressed(KeyCode::Z));
+    for gamepad in gamepads.iter() {
+        if gamepad_input.just_pressed(GamepadButton::new(gamepad, GamepadButtonType::West)) {
+            is_undo = true;
+        }
+        if gamepad_input.just_pressed(GamepadButton::new(gamepad, GamepadButtonType::North)) {
+            is_redo = true;
+        }
+    }
+    if is_undo {
+        undo_stroke(&mut stroke_store, &mut status);
+    } else if is_redo {
+        stroke_store.redo();
+    }
+}
+
+fn undo_stroke(stroke_store: &mut stroke::StrokeStore, status: &mut status::AppStatus) {
+    if stroke_store.undo() {
+        status.undo_count += 1;
+        status.control_chain = 0;
+        status.hyper_chain = 0;
+    }
+}
+
+fn record_stroke_chain(
+    mut stroke_events: EventReader<stroke::StrokeEvent>,
+    stroke_store: Res<stroke::StrokeStore>,
+    mut status: ResMut<status::AppStatus>,
+) {
+    for event in stroke_events.read() {
+        let index = match event {
+            stroke::StrokeEvent::Ended { index } => *index,
+            _ => continue,
+        };
+        let strokes = stroke_store.strokes();
+        let stroke = match strokes.get(index) {
+            Some(stroke) => stroke,
+            None => continue,
+        };
+        let gap_ms = match (
+            index.checked_sub(1).and_then(|prev| strokes.get(prev)),
+            stroke.samples.first(),
+        ) {
+            (Some(prev), Some(first)) => prev
+                .samples
+                .last()
+                .map(|last| first.time_ms.saturating_sub(last.time_ms)),
+            _ => None,
+        };
+        status.control_chain += 1;
+        status.hyper_chain = match gap_ms {
+            Some(gap_ms) if gap_ms <= HYPER_CHAIN_GAP_MS && status.hyper_chain > 0 => {
+                status.hyper_chain + 1
+            }
+            _ => 1,
+        };
+        status.max_alpha_count = status.max_alpha_count.max(strokes.len() as u32);
+        status.max_control_chain = status.max_control_chain.max(status.control_chain);
+        status.max_hyper_chain = status.max_hyper_chain.max(status.hyper_chain);
+    }
+}
+
+fn handle_repaint_key(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    key_input: Res<Input<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_input: Res<Input<GamepadButton>>,
+    confirm_query: Query<Entity, With<RepaintConfirm>>,
+    mut repaint: RepaintParams,
+) {
+    let mut is_repaint = key_input.just_pressed(KeyCode::Delete);
+    let mut is_cancel = key_input.just_pressed(KeyCode::Escape);
+    for gamepad in gamepads.iter() {
+        if gamepad_input.just_pressed(GamepadButton::new(gamepad, GamepadButtonType::Select)) {
+            is_repaint = true;
+        }
+        if gamepad_input.just_pressed(GamepadButton::new(gamepad, GamepadButtonType::East)) {
+            is_cancel = true;
+        }
+    }
+    if let Ok(confirm_entity) = confirm_query.get_single() {
+        if is_repaint {
+            commands.entity(confirm_entity).despawn_recursive();
+            repaint_canvas(&mut commands, &mut repaint);
+        } else if is_cancel {
+            commands.entity(confirm_entity).despawn_recursive();
+        }
+    } else if is_repaint {
+        open_repaint_confirm(&mut commands, &asset_server, &mut repaint);
+    }
+}
+
+fn handle_repaint_navigation(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut actions: Query<&mut RepaintAction>,
+    mut events: EventReader<NavEvent>,
+    confirm_query: Query<Entity, With<RepaintConfirm>>,
+    mut repaint: RepaintParams,
+) {
+    events.nav_iter().activated_in_query_foreach_mut(
+        &mut actions,
+        |mut action| match &mut *action {
+            RepaintAction::Open => {
+                if confirm_query.is_empty() {
+                    open_repaint_confirm(&mut commands, &asset_server, &mut repaint);
+                }
+            }
+            RepaintAction::Confirm => {
+                for entity in confirm_query.iter() {
+                    commands.entity(entity).despawn_recursive();
+                }
+                repaint_canvas(&mut commands, &mut repaint);
+            }
+            RepaintAction::Cancel => {
+                for entity in confirm_query.iter() {
+                    commands.entity(entity).despawn_recursive();
+                }
+            }
+        },
+    );
+}
+
+#[derive(SystemParam)]
+struct RepaintParams<'w, 's> {
+    status: ResMut<'w, status::AppStatus>,
+    stroke_store: Res<'w, stroke::StrokeStore>,
+    canvas_query: Query<'w, 's, Entity, With<stroke::StrokeCanvas>>,
+    eraser_query: Query<'w, 's, Entity, With<Eraser>>,
+    pause_query: Query<'w, 's, (), With<PausePanel>>,
+    audio_se_asset: Res<'w, audio::AudioSeAsset>,
+    settings: Res<'w, Persistent<settings::Settings>>,
+}
+
+fn open_repaint_confirm(
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
+    repaint: &mut RepaintParams,
+) {
+    if repaint.stroke_store.strokes().is_empty()
+        || !repaint.eraser_query.is_empty()
+        || !repaint.pause_query.is_empty()
+    {
+        return;
+    }
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                ..default()
+            },
+            RepaintConfirm,
+            OnPhase,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: ui::px_p(3.0),
+                        padding: UiRect::all(ui::px_p(6.0)),
+                        border: UiRect::all(ui::px_p(0.5)),
+                        ..default()
+                    },
+                    background_color: BG_COLOR.into(),
+                    border_color: MUTE_COLOR.into(),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        "Repaint the whole drawing?",
+                        TextStyle {
+                            font: asset_server.load(FONT),
+                            font_size: ui::FONT_SIZE,
+                            color: FG_COLOR,
+                        },
+                    ));
+                    parent
+                        .spawn(NodeBundle {
+                            style: Style {
+                                align_items: AlignItems::Center,
+                                column_gap: ui::px_p(4.0),
+                                ..default()
+                            },
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            ui::build_btn(
+                                parent,
+                                asset_server,
+                                (
+                                    RepaintAction::Cancel,
+                                    app::interaction::IaButton,
+                                    Focusable::default(),
+                                ),
+                                Style {
+                                    padding: UiRect::all(ui::px_p(ui::BTN_PADDING)),
+                                    ..default()
+                                },
+                                Some("Keep"),
+                                Some("arrow-left"),
+                            );
+                            ui::build_btn(
+                                parent,
+                                asset_server,
+                                (
+                                    RepaintAction::Confirm,
+                                    app::interaction::IaButton,
+                                    Focusable::default(),
+                                ),
+                                Style {
+                                    padding: UiRect::all(ui::px_p(ui::BTN_PADDING)),
+                                    ..default()
+                                },
+                                Some("Repaint"),
+                                Some("key_clear"),
+                            );
+                        });
+                });
+        });
+}
+
+fn repaint_canvas(commands: &mut Commands, repaint: &mut RepaintParams) {
+    if !repaint.eraser_query.is_empty() {
+        return;
+    }
+    let canvas_entity = match repaint.canvas_query.get_single() {
+        Ok(entity) => entity,
+        Err(_) => return,
+    };
+    repaint.status.repaint_count += 1;
+    repaint.status.control_chain = 0;
+    repaint.status.hyper_chain = 0;
+    audio::play_se(
+        audio::AudioSe::Pop,
+        commands,
+        &repaint.audio_se_asset,
+        repaint.settings.as_ref(),
+    );
+    let rect = shapes::Rectangle {
+        extents: CANVAS_SIZE,
+        origin: RectangleOrigin::TopLeft,
+    };
+    let tween = Tween::new(
+        EaseFunction::CubicInOut,
+        Duration::from_millis(ERASER_DURATION_MS),
+        TransformScaleLens {
+            start: Vec3::new(1.0, 0.0, 1.0),
+            end: Vec3::ONE,
+        },
+    )
+    .with_completed_event(ERASER_DONE_EVENT);
+    let eraser_entity = commands
+        .spawn((
+            ShapeBundle {
+                path: GeometryBuilder::build_as(&rect),
+                spatial: SpatialBundle {
+                    transform: Transform::from_xyz(
+                        -CANVAS_SIZE.x / 2.0,
+                        CANVAS_SIZE.y / 2.0,
+                        ERASER_Z_INDEX,
+                    )
+                    .with_scale(Vec3::new(1.0, 0.0, 1.0)),
+                    ..default()
+                },
+                ..default()
+            },
+            Fill::color(BG_COLOR),
+            Animator::new(tween),
+            Eraser,
+        ))
+        .id();
+    commands.entity(canvas_entity).add_child(eraser_entity);
+}
+
+fn handle_eraser_done(
+    mut commands: Commands,
+    mut tween_completed_events: EventReader<TweenCompleted>,
+    mut stroke_store: ResMut<stroke::StrokeStore>,
+) {
+    for tween_event in tween_completed_events.read() {
+        if tween_event.user_data == ERASER_DONE_EVENT {
+            if let Some(entity_commands) = commands.get_entity(tween_event.entity) {
+                entity_commands.despawn_recursive();
+            }
+            stroke_store.clear();
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use super::*;
+use crate::app::score;
+use bevy::window::PrimaryWindow;
+
+#[cfg(not(target_arch = "wasm32"))]
+use crate::app::screenshot;
+#[cfg(not(target_arch = "wasm32"))]
+use bevy::render::view::window::screenshot::ScreenshotManager;
+
+pub struct Phase;
+
+impl PhaseBase for Phase {
+    fn state(&self) -> GamePhase {
+        GamePhase::Evaluating
+    }
+    fn build(&self, app: &mut App) {
+        app.insert_resource(EvaluatingTimer(Timer::from_seconds(
+            EVALUATING_SECS,
+            TimerMode::Once,
+        )))
+        .add_systems(OnEnter(self.state()), phase_enter)
+        .add_systems(
+            Update,
+            (show_evaluating_text, handle_evaluating_timer).run_if(in_state(self.state())),
+        )
+        .add_systems(OnExit(self.state()), ui::despawn_ui::<OnPhase>);
+    }
+}
+
+#[derive(Component)]
+struct OnPhase;
+
+#[derive(Resource)]
+struct EvaluatingTimer(pub Timer);
+
+const EVALUATING_SECS: f32 = 0.6;
+const EVALUATING_TEXT_DELAY_SECS: f32 = 0.1;
+
+fn phase_enter(
+    mut round_params: RoundParams,
+    mut timer: ResMut<EvaluatingTimer>,
+    #[cfg(not(target_arch = "wasm32"))] main_window: Query<Entity, With<PrimaryWindow>>,
+    #[cfg(not(target_arch = "wasm32"))] mut screenshot_manager: ResMut<ScreenshotManager>,
+) {
+    let match_point = shape_match::match_polylines(
+        round_params.round.metric,
+        &round_params.round.target,
+        &round_params.stroke_store.polylines(),
+    )
+    .match_point;
+    let status = &mut round_params.status;
+    status.match_point = match_point;
+    status.score = score::ScoreBreakdown::new(status).total;
+    status.mark_timeline("ended");
+    check_achievements(
+        &mut round_params.status,
+        &mut round_params.ach_store,
+        &mut round_params.ach_info,
+    );
+    #[cfg(not(target_arch = "wasm32"))]
+    screenshot::shot_current(&main_window, &mut screenshot_manager, "score");
+    timer.0.reset();
+}
+
+fn show_evaluating_text(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    timer: Res<EvaluatingTimer>,
+    text_query: Query<Entity, With<OnPhase>>,
+) {
+    if timer.0.elapsed_secs() < EVALUATING_TEXT_DELAY_SECS || !text_query.is_empty() {
+        return;
+    }
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                ..default()
+            },
+            OnPhase,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "Evaluating...",
+                TextStyle {
+                    font: asset_server.load(FONT),
+                    font_size: ui::FONT_SIZE,
+                    color: FG_COLOR,
+                },
+            ));
+        });
+}
+
+fn handle_evaluating_timer(
+    mut timer: ResMut<EvaluatingTimer>,
+    mut next_phase: ResMut<NextState<GamePhase>>,
+    time: Res<Time>,
+) {
+    if timer.0.tick(time.delta()).just_finished() {
+        next_phase.set(GamePhase::Result);
+    }
+}
+
+fn check_achievements(
+    status: &mut ResMut<status::AppStatus>,
+    ach_store: &mut ResMut<Persistent<achievement::AchievementStore>>,
+    ach_info: &mut ResMut<achievement::AchievementInfo>,
+) {
+    if !ach_store.is_enabled {
+        return;
+    }
+    ach_store
+        .update(|store| {
+            for ach_def in achievement::achievements() {
+                if ach_def.count_round(status) {
+                    store.add_progress(ach_def.code(), 1);
+                }
+            }
+        })
+        .expect("failed to update achievement progress");
+    let mut is_changed = true;
+    while is_changed {
+        is_changed = false;
+        for code in ach_info.running_codes() {
+            let Some(ach_def) = achievement::fetch_ach_def(&code) else {
+                continue;
+            };
+            if ach_store.is_done(&code) {
+                continue;
+            }
+            let (_, _, is_done) = ach_def.check_done(status, ach_store);
+            let tier = ach_def.check_tier(status, ach_store);
+            let is_upgraded = tier.is_some() && tier > ach_store.fetch_tier(&code);
+            if is_done || is_upgraded {
+                ach_store
+                    .update(|store| match tier {
+                        Some(tier) => store.mark_tier(&code, tier, is_done),
+                        None => store.mark_done(&code),
+                    })
+                    .expect("failed to update achievement");
+                if !status.done_achievements.contains(&code) {
+                    status.done_achievements.push(code.clone());
+                }
+                ach_info.push_to_done(&code, tier);
+                is_changed = true;
+            }
+        }
+    }
+}
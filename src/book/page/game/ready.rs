@@ -0,0 +1,140 @@
+use super::*;
+
+pub struct Phase;
+
+impl PhaseBase for Phase {
+    fn state(&self) -> GamePhase {
+        GamePhase::Ready
+    }
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ReadyTimer(Timer::from_seconds(READY_SECS, TimerMode::Once)))
+            .add_systems(OnEnter(self.state()), phase_enter)
+            .add_systems(
+                Update,
+                (handle_target_grow, update_countdown).run_if(in_state(self.state())),
+            )
+            .add_systems(
+                OnExit(self.state()),
+                (ui::despawn_ui::<OnPhase>, phase_exit),
+            );
+    }
+}
+
+#[derive(Component)]
+struct OnPhase;
+
+#[derive(Component)]
+struct TargetGrow;
+
+#[derive(Component)]
+struct CountdownText;
+
+#[derive(Resource)]
+struct ReadyTimer(pub Timer);
+
+const READY_SECS: f32 = 3.0;
+const COUNTDOWN_FONT_SIZE_RATIO: f32 = 2.0;
+
+fn phase_enter(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    round: Res<GameRound>,
+    mut timer: ResMut<ReadyTimer>,
+    mut target_query: Query<&mut Visibility, With<TargetShape>>,
+) {
+    timer.0.reset();
+    for mut visibility in target_query.iter_mut() {
+        *visibility = Visibility::Hidden;
+    }
+    grow_target(&mut commands, &round);
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    top: ui::px_p(ui::PAGE_PADDING),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                ..default()
+            },
+            OnPhase,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    format_countdown(READY_SECS),
+                    TextStyle {
+                        font: asset_server.load(FONT_TITLE),
+                        font_size: ui::FONT_SIZE * COUNTDOWN_FONT_SIZE_RATIO,
+                        color: FG_COLOR,
+                    },
+                ),
+                CountdownText,
+            ));
+        });
+}
+
+fn phase_exit(
+    ae_query: Query<Entity, With<TargetGrow>>,
+    mut target_query: Query<&mut Visibility, With<TargetShape>>,
+    mut ae_status: ResMut<anime_effect::AnimeEffectStatus>,
+) {
+    for entity in ae_query.iter() {
+        anime_effect::despawn_anime_effect(entity, &mut ae_status);
+    }
+    for mut visibility in target_query.iter_mut() {
+        *visibility = Visibility::Inherited;
+    }
+}
+
+fn update_countdown(
+    mut timer: ResMut<ReadyTimer>,
+    mut text_query: Query<&mut Text, With<CountdownText>>,
+    mut next_phase: ResMut<NextState<GamePhase>>,
+    time: Res<Time>,
+) {
+    if timer.0.tick(time.delta()).just_finished() {
+        next_phase.set(GamePhase::Drawing);
+        return;
+    }
+    if let Ok(mut text) = text_query.get_single_mut() {
+        text.sections[0].value = format_countdown(timer.0.remaining_secs());
+    }
+}
+
+fn format_countdown(remaining_secs: f32) -> String {
+    format!("{}", remaining_secs.ceil() as u32)
+}
+
+fn grow_target(commands: &mut Commands, round: &GameRound) {
+    anime_effect::insert_anime_effect(
+        commands,
+        anime_effect::AnimeEffectParam {
+            kind: anime_effect::AnimeEffectKind::Grow,
+            color: TARGET_COLOR,
+            pos_1: CANVAS_POS,
+            pos_2: CANVAS_POS,
+            width_start: TARGET_LINE_W * 0.5,
+            width_end: TARGET_LINE_W,
+            polylines: round.plant.growth_polylines(),
+        },
+        TargetGrow,
+    );
+}
+
+fn handle_target_grow(
+    ae_query: Query<(Entity, &anime_effect::AnimeEffect), With<TargetGrow>>,
+    mut target_query: Query<&mut Visibility, With<TargetShape>>,
+    mut ae_status: ResMut<anime_effect::AnimeEffectStatus>,
+) {
+    for (entity, ae) in ae_query.iter() {
+        if ae.delta >= 1.0 {
+            anime_effect::despawn_anime_effect(entity, &mut ae_status);
+            for mut visibility in target_query.iter_mut() {
+                *visibility = Visibility::Inherited;
+            }
+        }
+    }
+}
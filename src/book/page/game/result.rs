@@ -0,0 +1,540 @@
+use super::*;
+use crate::app::{leaderboard, score, settings, sync};
+use bevy_tweening::{lens::TransformScaleLens, *};
+use std::time::Duration;
+
+pub struct Phase;
+
+impl PhaseBase for Phase {
+    fn state(&self) -> GamePhase {
+        GamePhase::Result
+    }
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(self.state()), phase_enter)
+            .add_systems(
+                Update,
+                (handle_ui_navigation, handle_name_input)
+                    .after(NavRequestSystem)
+                    .run_if(in_state(self.state())),
+            )
+            .add_systems(
+                OnExit(self.state()),
+                (ui::despawn_ui::<OnPhase>, phase_exit),
+            );
+    }
+}
+
+#[derive(Component)]
+struct OnPhase;
+
+#[derive(Component)]
+struct StrokeGrow;
+
+#[derive(Component)]
+struct RecordEntry;
+
+#[derive(Component)]
+struct NameInput(String);
+
+const RESULT_PANEL_PADDING: f32 = 16.0;
+const NAME_CURSOR: &str = "_";
+const DEFAULT_PLAYER_NAME: &str = "Player";
+
+#[allow(clippy::too_many_arguments)]
+fn handle_ui_navigation(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut actions: Query<&mut ButtonAction>,
+    mut events: EventReader<NavEvent>,
+    mut round_params: RoundParams,
+    mut next_phase: ResMut<NextState<GamePhase>>,
+    mut leaderboard: ResMut<Persistent<leaderboard::Leaderboard>>,
+    mut settings: ResMut<Persistent<settings::Settings>>,
+    mut sync_queue: ResMut<Persistent<sync::SyncQueue>>,
+    input_query: Query<&NameInput>,
+    entry_query: Query<Entity, With<RecordEntry>>,
+) {
+    events
+        .nav_iter()
+        .activated_in_query_foreach_mut(&mut actions, |action| match *action {
+            ButtonAction::Retry => {
+                let config = RoundConfig {
+                    seed: round_params.round.plant.param.seed,
+                    metric: round_params.round.metric,
+                };
+                start_round(&mut round_params, config);
+                next_phase.set(GamePhase::Ready);
+            }
+            ButtonAction::SaveRecord => {
+                let player_name = match input_query.get_single() {
+                    Ok(input) => input.0.trim().to_string(),
+                    Err(_) => return,
+                };
+                let is_named = !player_name.is_empty();
+                let player_name = if is_named {
+                    player_name
+                } else {
+                    String::from(DEFAULT_PLAYER_NAME)
+                };
+                let record =
+                    leaderboard::LeaderboardRecord::from_status(&round_params.status, &player_name);
+                round_params.status.highlight_uid = record.uid().to_string();
+                round_params.status.player_name = player_name.clone();
+                leaderboard
+                    .update(|leaderboard| {
+                        leaderboard.store(record.clone());
+                    })
+                    .expect("failed to store leaderboard record");
+                sync::push_record(&mut sync_queue, &settings, &record);
+                if is_named {
+                    settings
+                        .update(|settings| {
+                            settings.update_last_player(player_name.as_str());
+                        })
+                        .expect("failed to update last player");
+                }
+                let message = if is_named {
+                    format!("Saved as {}", player_name)
+                } else {
+                    format!("No name entered, saved as {}", player_name)
+                };
+                for entity in entry_query.iter() {
+                    commands
+                        .entity(entity)
+                        .despawn_descendants()
+                        .with_children(|parent| {
+                            parent.spawn(TextBundle::from_section(
+                                message.clone(),
+                                TextStyle {
+                                    font: asset_server.load(FONT),
+                                    font_size: ui::FONT_SIZE,
+                                    color: SECONDARY_COLOR,
+                                },
+                            ));
+                        });
+                }
+            }
+            _ => (),
+        });
+}
+
+fn handle_name_input(
+    mut char_events: EventReader<ReceivedCharacter>,
+    key_input: Res<Input<KeyCode>>,
+    mut input_query: Query<(&mut NameInput, &mut Text)>,
+) {
+    let (mut input, mut text) = match input_query.get_single_mut() {
+        Ok(result) => result,
+        Err(_) => {
+            char_events.clear();
+            return;
+        }
+    };
+    let mut name = input.0.clone();
+    for event in char_events.read() {
+        if (event.char.is_ascii_alphanumeric() || " -_.".contains(event.char))
+            && name.chars().count() < leaderboard::MAX_PLAYER_NAME_LENGTH
+        {
+            name.push(event.char);
+        }
+    }
+    if key_input.just_pressed(KeyCode::Back) {
+        name.pop();
+    }
+    if name != input.0 {
+        text.sections[0].value = format!("{}{}", name, NAME_CURSOR);
+        input.0 = name;
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn phase_enter(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    status: Res<status::AppStatus>,
+    stroke_store: Res<stroke::StrokeStore>,
+    leaderboard: Res<Persistent<leaderboard::Leaderboard>>,
+    settings: Res<Persistent<settings::Settings>>,
+    ach_store: Res<Persistent<achievement::AchievementStore>>,
+    ach_info: Res<achievement::AchievementInfo>,
+) {
+    let is_new_record =
+        leaderboard.is_new_record(&leaderboard::LeaderboardRecord::from_status(&status, ""));
+    anime_effect::insert_anime_effect(
+        &mut commands,
+        anime_effect::AnimeEffectParam {
+            kind: anime_effect::AnimeEffectKind::Grow,
+            color: FG_COLOR.with_l(0.55),
+            pos_1: CANVAS_POS,
+            pos_2: CANVAS_POS,
+            width_start: stroke::PEN_LINE_W * 0.5,
+            width_end: stroke::PEN_LINE_W * 1.2,
+            polylines: stroke_store.polylines(),
+        },
+        StrokeGrow,
+    );
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::FlexEnd,
+                    padding: UiRect::right(ui::px_p(RESULT_PANEL_PADDING)),
+                    ..default()
+                },
+                ..default()
+            },
+            OnPhase,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: ui::px_p(3.0),
+                        padding: UiRect::all(ui::px_p(6.0)),
+                        border: UiRect::all(ui::px_p(0.5)),
+                        ..default()
+                    },
+                    background_color: BG_COLOR.into(),
+                    border_color: MUTE_COLOR.into(),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    build_score_breakdown(parent, &asset_server, &status);
+                    if ach_store.is_enabled {
+                        build_ach_progress(parent, &asset_server, &ach_store, &ach_info);
+                    }
+                    if is_new_record {
+                        build_record_entry(parent, &asset_server, settings.fetch_last_player());
+                    }
+                    parent
+                        .spawn(NodeBundle {
+                            style: Style {
+                                align_items: AlignItems::Center,
+                                column_gap: ui::px_p(4.0),
+                                margin: UiRect::top(ui::px_p(3.0)),
+                                ..default()
+                            },
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            ui::build_btn(
+                                parent,
+                                &asset_server,
+                                (
+                                    ButtonAction::BackToMainMenu,
+                                    app::interaction::IaButton,
+                                    Focusable::default(),
+                                ),
+                                Style {
+                                    padding: UiRect::all(ui::px_p(ui::BTN_PADDING)),
+                                    ..default()
+                                },
+                                Some("Menu"),
+                                Some("arrow-left"),
+                            );
+                            ui::build_btn(
+                                parent,
+                                &asset_server,
+                                (
+                                    ButtonAction::Retry,
+                                    app::interaction::IaButton,
+                                    if is_new_record {
+                                        Focusable::default()
+                                    } else {
+                                        Focusable::new().prioritized()
+                                    },
+                                ),
+                                Style {
+                                    padding: UiRect::all(ui::px_p(ui::BTN_PADDING)),
+                                    ..default()
+                                },
+                                Some("Retry"),
+                                Some("play"),
+                            );
+                        });
+                });
+        });
+}
+
+fn phase_exit(
+    ae_query: Query<Entity, With<StrokeGrow>>,
+    mut ae_status: ResMut<anime_effect::AnimeEffectStatus>,
+) {
+    for entity in ae_query.iter() {
+        anime_effect::despawn_anime_effect(entity, &mut ae_status);
+    }
+}
+
+fn build_ach_progress(
+    parent: &mut ChildBuilder,
+    asset_server: &Res<AssetServer>,
+    ach_store: &achievement::AchievementStore,
+    ach_info: &achievement::AchievementInfo,
+) {
+    for code in ach_info.running_codes() {
+        let Some(ach_def) = achievement::fetch_ach_def(&code) else {
+            continue;
+        };
+        let Some((current, target)) = ach_def.progress(ach_store) else {
+            continue;
+        };
+        let next_tier = achievement::next_tier(ach_store.fetch_tier(&code));
+        parent
+            .spawn(NodeBundle {
+                style: Style {
+                    width: ui::px_p(RESULT_ROW_W),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::SpaceBetween,
+                    column_gap: ui::px_p(3.0),
+                    ..default()
+                },
+                ..default()
+            })
+            .with_children(|parent| {
+                parent
+                    .spawn(NodeBundle {
+                        style: Style {
+                            align_items: AlignItems::Center,
+                            column_gap: ui::px_p(2.0),
+                            ..default()
+                        },
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        parent.spawn(ImageBundle {
+                            style: Style {
+                                width: Val::Px(ui::ICON_SIZE),
+                                height: Val::Px(ui::ICON_SIZE),
+                                ..default()
+                            },
+                            image: UiImage::new(asset_server.load(ach_def.icon_path())),
+                            ..default()
+                        });
+                        parent.spawn(TextBundle::from_section(
+                            ach_def.tier_name(next_tier),
+                            TextStyle {
+                                font: asset_server.load(FONT),
+                                font_size: ui::FONT_SIZE * 0.6,
+                                color: ach_def.color(),
+                            },
+                        ));
+                    });
+                achievement::build_progress_ui(parent, asset_server, ach_def, current, target);
+            });
+    }
+}
+
+fn build_record_entry(
+    parent: &mut ChildBuilder,
+    asset_server: &Res<AssetServer>,
+    last_player: &str,
+) {
+    let player_name: String = if last_player.is_empty() {
+        DEFAULT_PLAYER_NAME
+    } else {
+        last_player
+    }
+    .chars()
+    .take(leaderboard::MAX_PLAYER_NAME_LENGTH)
+    .collect();
+    parent
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    row_gap: ui::px_p(2.0),
+                    margin: UiRect::top(ui::px_p(3.0)),
+                    ..default()
+                },
+                ..default()
+            },
+            RecordEntry,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "New Record!",
+                TextStyle {
+                    font: asset_server.load(FONT),
+                    font_size: ui::FONT_SIZE,
+                    color: SECONDARY_COLOR,
+                },
+            ));
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        align_items: AlignItems::Center,
+                        column_gap: ui::px_p(3.0),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent
+                        .spawn(NodeBundle {
+                            style: Style {
+                                width: ui::px_p(RESULT_ROW_W * 0.6),
+                                padding: UiRect::all(ui::px_p(1.0)),
+                                border: UiRect::bottom(ui::px_p(0.5)),
+                                ..default()
+                            },
+                            border_color: FG_COLOR.into(),
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            parent.spawn((
+                                TextBundle::from_section(
+                                    format!("{}{}", player_name, NAME_CURSOR),
+                                    TextStyle {
+                                        font: asset_server.load(FONT),
+                                        font_size: ui::FONT_SIZE,
+                                        color: FG_COLOR,
+                                    },
+                                ),
+                                NameInput(player_name),
+                            ));
+                        });
+                    ui::build_btn(
+                        parent,
+                        asset_server,
+                        (
+                            ButtonAction::SaveRecord,
+                            app::interaction::IaButton,
+                            Focusable::new().prioritized(),
+                        ),
+                        Style {
+                            padding: UiRect::all(ui::px_p(ui::BTN_PADDING)),
+                            ..default()
+                        },
+                        Some("Save"),
+                        Some("trophy-fill"),
+                    );
+                });
+        });
+}
+
+const COUNTER_DELAY_MS: u64 = 300;
+const COUNTER_DURATION_MS: u64 = 600;
+const GRADE_DURATION_MS: u64 = 400;
+const GRADE_FONT_SIZE_RATIO: f32 = 3.0;
+const RESULT_ROW_W: f32 = 80.0;
+
+fn build_score_breakdown(
+    parent: &mut ChildBuilder,
+    asset_server: &Res<AssetServer>,
+    status: &status::AppStatus,
+) {
+    let breakdown = score::ScoreBreakdown::new(status);
+    let rows = [
+        (
+            format!("Match {:.1}%", status.match_point as f32 / 10.0),
+            "+",
+            breakdown.match_score,
+        ),
+        (
+            format!("Time {}", format_time(status.time).trim()),
+            "+",
+            breakdown.time_bonus,
+        ),
+        (
+            format!("Undo x{}", status.undo_count),
+            "-",
+            breakdown.undo_penalty,
+        ),
+        (
+            format!("Repaint x{}", status.repaint_count),
+            "-",
+            breakdown.repaint_penalty,
+        ),
+        (String::from("Score"), "", breakdown.total),
+    ];
+    let row_count = rows.len();
+    let text_style = TextStyle {
+        font: asset_server.load(FONT),
+        font_size: ui::FONT_SIZE,
+        color: FG_COLOR,
+    };
+    for (i, (label, prefix, value)) in rows.into_iter().enumerate() {
+        let is_total = i == row_count - 1;
+        parent
+            .spawn(NodeBundle {
+                style: Style {
+                    width: ui::px_p(RESULT_ROW_W),
+                    justify_content: JustifyContent::SpaceBetween,
+                    border: if is_total {
+                        UiRect::top(ui::px_p(0.5))
+                    } else {
+                        UiRect::default()
+                    },
+                    padding: if is_total {
+                        UiRect::top(ui::px_p(2.0))
+                    } else {
+                        UiRect::default()
+                    },
+                    ..default()
+                },
+                border_color: MUTE_COLOR.into(),
+                ..default()
+            })
+            .with_children(|parent| {
+                parent.spawn(TextBundle::from_section(label, text_style.clone()));
+                let tween = Delay::new(Duration::from_millis(COUNTER_DELAY_MS * (i as u64 + 1)))
+                    .then(Tween::new(
+                        EaseFunction::QuadraticOut,
+                        Duration::from_millis(COUNTER_DURATION_MS),
+                        ScoreCounterLens { prefix, value },
+                    ));
+                parent.spawn((
+                    TextBundle::from_section(format!("{}0", prefix), text_style.clone()),
+                    Animator::new(tween),
+                ));
+            });
+    }
+    let tween = Delay::new(Duration::from_millis(
+        COUNTER_DELAY_MS * (row_count as u64 + 1) + COUNTER_DURATION_MS,
+    ))
+    .then(Tween::new(
+        EaseFunction::BackOut,
+        Duration::from_millis(GRADE_DURATION_MS),
+        TransformScaleLens {
+            start: Vec3::ZERO,
+            end: Vec3::ONE,
+        },
+    ));
+    parent.spawn((
+        TextBundle {
+            text: Text::from_section(
+                breakdown.grade.letter(),
+                TextStyle {
+                    font: asset_server.load(FONT_TITLE),
+                    font_size: ui::FONT_SIZE * GRADE_FONT_SIZE_RATIO,
+                    color: SECONDARY_COLOR,
+                },
+            ),
+            transform: Transform::from_scale(Vec3::ZERO),
+            ..default()
+        },
+        Animator::new(tween),
+    ));
+}
+
+struct ScoreCounterLens {
+    prefix: &'static str,
+    value: u32,
+}
+
+impl Lens<Text> for ScoreCounterLens {
+    fn lerp(&mut self, target: &mut Text, ratio: f32) {
+        target.sections[0].value = format!(
+            "{}{}",
+            self.prefix,
+            (self.value as f32 * ratio).round() as u32
+        );
+    }
+}
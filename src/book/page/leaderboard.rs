@@ -0,0 +1,1030 @@
+use crate::{
+    app::{
+        anime_effect, integrity, interaction, leaderboard, settings, status, sync, theme::*, ui,
+    },
+    book::page::*,
+};
+use bevy::ecs::system::SystemParam;
+use bevy_persistent::prelude::*;
+use bevy_ui_navigation::{prelude::*, NavRequestSystem};
+
+const PAGE_CODE: &str = "leaderboard";
+const PAGE_NAME: &str = "Leaderboard";
+const PAGE_ICON: &str = "trophy-fill";
+
+pub struct Page;
+
+impl PageBase for Page {
+    fn code(&self) -> &str {
+        PAGE_CODE
+    }
+    fn name(&self) -> &str {
+        PAGE_NAME
+    }
+    fn icon(&self) -> &str {
+        PAGE_ICON
+    }
+    fn state(&self) -> PageState {
+        PageState::Leaderboard
+    }
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(self.state()),
+            (interaction::reset_default_focus, page_enter),
+        )
+        .add_systems(
+            Update,
+            (
+                handle_ui_navigation,
+                handle_delete_navigation,
+                update_leaderboard_list,
+                interaction::handle_default_focus,
+            )
+                .after(NavRequestSystem)
+                .run_if(in_state(self.state())),
+        )
+        .add_systems(
+            OnExit(self.state()),
+            (anime_effect::clear_anime_effect, ui::despawn_ui::<OnPage>),
+        );
+    }
+}
+
+#[derive(Component)]
+struct OnPage;
+
+#[derive(Component)]
+enum ButtonAction {
+    MoveToPage(PageState),
+    SwitchList(leaderboard::LeaderboardField),
+    SwitchScope(ListScope),
+    ShowRecord(String),
+    CloseRecord,
+    ExportRecord(String),
+    ReplayRecord(u64),
+}
+
+#[derive(Component)]
+enum DeleteAction {
+    Open(String),
+    Confirm(String),
+    Cancel,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum ListScope {
+    Local,
+    Global,
+}
+
+impl ListScope {
+    fn name(&self) -> &str {
+        match self {
+            ListScope::Local => "Local",
+            ListScope::Global => "Global",
+        }
+    }
+}
+
+#[derive(Component)]
+struct LeaderboardList {
+    field: leaderboard::LeaderboardField,
+    scope: ListScope,
+}
+
+#[derive(Component)]
+struct ListTabMark(leaderboard::LeaderboardField);
+
+#[derive(Component)]
+struct ScopeTabMark(ListScope);
+
+#[derive(Component)]
+struct RecordDetail;
+
+#[derive(Component)]
+struct ScreenshotPanel;
+
+#[derive(Component)]
+struct ScreenshotImage;
+
+#[derive(Component)]
+struct DetailMessage;
+
+#[derive(Component)]
+struct DeleteConfirm;
+
+const LIST_W: f32 = 160.0;
+const RANK_COL_W: f32 = 16.0;
+const NAME_COL_W: f32 = 60.0;
+const VALUE_COL_W: f32 = 36.0;
+const ROW_PADDING: f32 = 1.5;
+const TAB_MARK_H: f32 = 0.8;
+const SCREENSHOT_W: f32 = 320.0; // px
+const SCREENSHOT_H: f32 = 180.0; // px
+const DETAIL_PADDING: f32 = 6.0;
+
+fn page_enter(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    settings: Res<Persistent<settings::Settings>>,
+    mut sync_timer: ResMut<sync::SyncTimer>,
+) {
+    let is_sync_enabled = settings.is_enabled("sync");
+    if is_sync_enabled {
+        sync_timer.request();
+    }
+    commands
+        .spawn((build_page_layout(), OnPage))
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        width: Val::Percent(100.0),
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::SpaceBetween,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    build_game_title(parent, &asset_server);
+                    build_page_title(parent, &asset_server, PAGE_NAME, PAGE_ICON);
+                    parent
+                        .spawn(NodeBundle {
+                            style: Style {
+                                flex_grow: 1.0,
+                                flex_direction: FlexDirection::Column,
+                                align_items: AlignItems::Center,
+                                justify_content: JustifyContent::Center,
+                                row_gap: ui::px_p(4.0),
+                                ..default()
+                            },
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            if is_sync_enabled {
+                                build_scope_tabs(parent, &asset_server);
+                            }
+                            build_list_tabs(parent, &asset_server);
+                            parent.spawn((
+                                NodeBundle {
+                                    style: Style {
+                                        width: ui::px_p(LIST_W),
+                                        flex_direction: FlexDirection::Column,
+                                        padding: UiRect::all(ui::px_p(3.0)),
+                                        border: UiRect::all(ui::px_p(0.5)),
+                                        ..default()
+                                    },
+                                    background_color: BG_COLOR.into(),
+                                    border_color: MUTE_COLOR.into(),
+                                    ..default()
+                                },
+                                LeaderboardList {
+                                    field: leaderboard::LEADERBOARD_LISTS[0],
+                                    scope: ListScope::Local,
+                                },
+                            ));
+                        });
+                });
+            ui::build_icon_btn(
+                parent,
+                &asset_server,
+                (
+                    ButtonAction::MoveToPage(PageState::Menu),
+                    app::interaction::IaButton,
+                    Focusable::default(),
+                    app::interaction::IaDefaultFocus,
+                ),
+                Style {
+                    position_type: PositionType::Absolute,
+                    bottom: ui::px_p(ui::PAGE_PADDING),
+                    left: ui::px_p(ui::PAGE_PADDING),
+                    ..default()
+                },
+                "arrow-left-bold_x1.5",
+            );
+        });
+}
+
+fn build_scope_tabs(parent: &mut ChildBuilder, asset_server: &Res<AssetServer>) {
+    parent
+        .spawn(NodeBundle {
+            style: Style {
+                align_items: AlignItems::End,
+                column_gap: ui::px_p(3.0),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            for scope in [ListScope::Local, ListScope::Global] {
+                parent
+                    .spawn(NodeBundle {
+                        style: Style {
+                            flex_direction: FlexDirection::Column,
+                            align_items: AlignItems::Center,
+                            row_gap: ui::px_p(1.0),
+                            ..default()
+                        },
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        ui::build_btn(
+                            parent,
+                            asset_server,
+                            (
+                                ButtonAction::SwitchScope(scope),
+                                app::interaction::IaButton,
+                                Focusable::default(),
+                            ),
+                            Style {
+                                padding: UiRect::all(ui::px_p(ui::BTN_PADDING * 0.6)),
+                                ..default()
+                            },
+                            Some(scope.name()),
+                            None,
+                        );
+                        parent.spawn((
+                            NodeBundle {
+                                style: Style {
+                                    width: Val::Percent(100.0),
+                                    height: ui::px_p(TAB_MARK_H),
+                                    ..default()
+                                },
+                                ..default()
+                            },
+                            ScopeTabMark(scope),
+                        ));
+                    });
+            }
+        });
+}
+
+fn build_list_tabs(parent: &mut ChildBuilder, asset_server: &Res<AssetServer>) {
+    parent
+        .spawn(NodeBundle {
+            style: Style {
+                align_items: AlignItems::End,
+                column_gap: ui::px_p(3.0),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            for field in leaderboard::LEADERBOARD_LISTS {
+                parent
+                    .spawn(NodeBundle {
+                        style: Style {
+                            flex_direction: FlexDirection::Column,
+                            align_items: AlignItems::Center,
+                            row_gap: ui::px_p(1.0),
+                            ..default()
+                        },
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        ui::build_btn(
+                            parent,
+                            asset_server,
+                            (
+                                ButtonAction::SwitchList(field),
+                                app::interaction::IaButton,
+                                Focusable::default(),
+                            ),
+                            Style {
+                                padding: UiRect::all(ui::px_p(ui::BTN_PADDING * 0.6)),
+                                ..default()
+                            },
+                            Some(field.name()),
+                            None,
+                        );
+                        parent.spawn((
+                            NodeBundle {
+                                style: Style {
+                                    width: Val::Percent(100.0),
+                                    height: ui::px_p(TAB_MARK_H),
+                                    ..default()
+                                },
+                                ..default()
+                            },
+                            ListTabMark(field),
+                        ));
+                    });
+            }
+        });
+}
+
+#[allow(clippy::too_many_arguments)]
+fn update_leaderboard_list(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    leaderboard: Res<Persistent<leaderboard::Leaderboard>>,
+    global: Res<sync::GlobalLeaderboard>,
+    report: Res<integrity::IntegrityReport>,
+    status: Res<status::AppStatus>,
+    list_query: Query<(Entity, Ref<LeaderboardList>)>,
+    mut tab_mark_query: Query<(&ListTabMark, &mut BackgroundColor), Without<ScopeTabMark>>,
+    mut scope_mark_query: Query<(&ScopeTabMark, &mut BackgroundColor), Without<ListTabMark>>,
+) {
+    let (entity, list) = match list_query.get_single() {
+        Ok(result) => result,
+        Err(_) => return,
+    };
+    let is_global_changed = list.scope == ListScope::Global && global.is_changed();
+    if !list.is_changed() && !is_global_changed {
+        return;
+    }
+    for (tab_mark, mut bg_color) in tab_mark_query.iter_mut() {
+        *bg_color = if tab_mark.0 == list.field {
+            SECONDARY_COLOR.into()
+        } else {
+            Color::NONE.into()
+        };
+    }
+    for (tab_mark, mut bg_color) in scope_mark_query.iter_mut() {
+        *bg_color = if tab_mark.0 == list.scope {
+            SECONDARY_COLOR.into()
+        } else {
+            Color::NONE.into()
+        };
+    }
+    let (records, message) = match list.scope {
+        ListScope::Local => {
+            let records = leaderboard.fetch_ranked_records(list.field);
+            let message = records
+                .iter()
+                .any(|(_, record)| report.is_record_flagged(record.uid()))
+                .then(|| String::from("Records in red failed verification"));
+            (records, message)
+        }
+        ListScope::Global => (
+            global
+                .leaderboard
+                .as_ref()
+                .map(|leaderboard| leaderboard.fetch_ranked_records(list.field))
+                .unwrap_or_default(),
+            match (&global.leaderboard, &global.last_error) {
+                (_, Some(error)) => Some(format!("Sync failed: {}", error)),
+                (None, None) => Some(String::from("Syncing...")),
+                _ => None,
+            },
+        ),
+    };
+    commands
+        .entity(entity)
+        .despawn_descendants()
+        .with_children(|parent| {
+            build_list_row(
+                parent,
+                &asset_server,
+                (),
+                ["", "Name", list.field.name(), "Date"],
+                MUTE_COLOR,
+            );
+            for (rank, record) in records.iter() {
+                let is_flagged =
+                    list.scope == ListScope::Local && report.is_record_flagged(record.uid());
+                let color = if is_flagged {
+                    ALERT_COLOR
+                } else if record.uid() == status.highlight_uid {
+                    SECONDARY_COLOR
+                } else {
+                    FG_COLOR
+                };
+                let columns = [
+                    rank.to_string(),
+                    record.player_name.clone(),
+                    format_value(list.field, record.fetch(list.field)),
+                    format_date(record.ended_at.as_str()).to_string(),
+                ];
+                let columns = columns.each_ref().map(|column| column.as_str());
+                if list.scope == ListScope::Local {
+                    build_list_row(
+                        parent,
+                        &asset_server,
+                        (
+                            Button,
+                            Interaction::default(),
+                            ButtonAction::ShowRecord(record.uid().to_string()),
+                            app::interaction::IaButton,
+                            Focusable::default(),
+                        ),
+                        columns,
+                        color,
+                    );
+                } else {
+                    build_list_row(parent, &asset_server, (), columns, color);
+                }
+            }
+            let message =
+                message.or_else(|| records.is_empty().then(|| String::from("No records yet")));
+            if let Some(message) = message {
+                parent.spawn(
+                    TextBundle::from_section(
+                        message,
+                        TextStyle {
+                            font: asset_server.load(FONT),
+                            font_size: ui::FONT_SIZE,
+                            color: MUTE_COLOR,
+                        },
+                    )
+                    .with_style(Style {
+                        align_self: AlignSelf::Center,
+                        margin: UiRect::vertical(ui::px_p(6.0)),
+                        ..default()
+                    }),
+                );
+            }
+        });
+}
+
+fn build_list_row(
+    parent: &mut ChildBuilder,
+    asset_server: &Res<AssetServer>,
+    bundle: impl Bundle,
+    columns: [&str; 4],
+    color: Color,
+) {
+    parent
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    align_items: AlignItems::Center,
+                    padding: UiRect::vertical(ui::px_p(ROW_PADDING)),
+                    ..default()
+                },
+                ..default()
+            },
+            bundle,
+        ))
+        .with_children(|parent| {
+            let text_style = TextStyle {
+                font: asset_server.load(FONT),
+                font_size: ui::FONT_SIZE,
+                color,
+            };
+            let [rank, name, value, date] = columns;
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        width: ui::px_p(RANK_COL_W),
+                        justify_content: JustifyContent::Center,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    if rank.is_empty() {
+                        let icon = asset_server.load("images/icons/list-numbers.png");
+                        parent.spawn(ImageBundle {
+                            style: Style {
+                                width: Val::Px(ui::ICON_SIZE),
+                                height: Val::Px(ui::ICON_SIZE),
+                                ..default()
+                            },
+                            image: UiImage::new(icon),
+                            ..default()
+                        });
+                    } else {
+                        parent.spawn(TextBundle::from_section(rank, text_style.clone()));
+                    }
+                });
+            for (text, width, justify_content) in [
+                (name, NAME_COL_W, JustifyContent::Start),
+                (value, VALUE_COL_W, JustifyContent::End),
+            ] {
+                parent
+                    .spawn(NodeBundle {
+                        style: Style {
+                            width: ui::px_p(width),
+                            justify_content,
+                            overflow: Overflow::clip(),
+                            ..default()
+                        },
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle::from_section(text, text_style.clone()));
+                    });
+            }
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_grow: 1.0,
+                        justify_content: JustifyContent::End,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(date, text_style.clone()));
+                });
+        });
+}
+
+fn format_value(field: leaderboard::LeaderboardField, value: u32) -> String {
+    match field {
+        leaderboard::LeaderboardField::Time => format!("{}.{:0>2}", value / 100, value % 100),
+        _ => value.to_string(),
+    }
+}
+
+fn format_date(datetime: &str) -> &str {
+    datetime.split('_').next().unwrap_or_default()
+}
+
+fn format_datetime(datetime: &str) -> String {
+    datetime
+        .chars()
+        .take(19)
+        .collect::<String>()
+        .replace('_', " ")
+}
+
+#[derive(SystemParam)]
+struct DetailParams<'w, 's> {
+    leaderboard: ResMut<'w, Persistent<leaderboard::Leaderboard>>,
+    report: Res<'w, integrity::IntegrityReport>,
+    replay_seed: ResMut<'w, game::ReplaySeed>,
+    images: ResMut<'w, Assets<Image>>,
+    detail_query: Query<'w, 's, Entity, With<RecordDetail>>,
+    message_query: Query<'w, 's, &'static mut Text, With<DetailMessage>>,
+    default_focus_query: Query<'w, 's, Entity, With<interaction::IaDefaultFocus>>,
+}
+
+#[allow(clippy::too_many_arguments)]
+fn handle_ui_navigation(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut actions: Query<&mut ButtonAction>,
+    mut events: EventReader<NavEvent>,
+    mut requests: EventWriter<NavRequest>,
+    mut page_state: ResMut<NextState<PageState>>,
+    mut list_query: Query<&mut LeaderboardList>,
+    mut detail_params: DetailParams,
+) {
+    events.nav_iter().activated_in_query_foreach_mut(
+        &mut actions,
+        |mut action| match &mut *action {
+            ButtonAction::MoveToPage(state) => page_state.set(*state),
+            ButtonAction::SwitchList(field) => {
+                if let Ok(mut list) = list_query.get_single_mut() {
+                    if list.field != *field {
+                        list.field = *field;
+                    }
+                }
+            }
+            ButtonAction::SwitchScope(scope) => {
+                if let Ok(mut list) = list_query.get_single_mut() {
+                    if list.scope != *scope {
+                        list.scope = *scope;
+                    }
+                }
+            }
+            ButtonAction::ShowRecord(uid) => {
+                if !detail_params.detail_query.is_empty() {
+                    return;
+                }
+                if let Some(record) = detail_params.leaderboard.fetch_record(uid) {
+                    let close_btn = build_record_detail(
+                        &mut commands,
+                        &asset_server,
+                        &mut detail_params.images,
+                        &record,
+                        detail_params.report.is_record_flagged(uid),
+                    );
+                    requests.send(NavRequest::FocusOn(close_btn));
+                }
+            }
+            ButtonAction::CloseRecord => {
+                close_record_detail(&mut commands, &mut requests, &detail_params);
+            }
+            ButtonAction::ExportRecord(uid) => {
+                if let Some(record) = detail_params.leaderboard.fetch_record(uid) {
+                    let message = match app::export::export_record(
+                        &record,
+                        &app::screenshot::fetch_screenshots_dir_path(),
+                    ) {
+                        Ok(path) => format!("Exported to {}", path.display()),
+                        Err(error) => format!("Export failed: {}", error),
+                    };
+                    for mut text in detail_params.message_query.iter_mut() {
+                        text.sections[0].value = message.clone();
+                    }
+                }
+            }
+            ButtonAction::ReplayRecord(seed) => {
+                detail_params.replay_seed.0 = Some(*seed);
+                page_state.set(PageState::Game);
+            }
+        },
+    );
+}
+
+#[allow(clippy::too_many_arguments)]
+fn handle_delete_navigation(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut actions: Query<&mut DeleteAction>,
+    mut events: EventReader<NavEvent>,
+    mut requests: EventWriter<NavRequest>,
+    mut list_query: Query<&mut LeaderboardList>,
+    confirm_query: Query<Entity, With<DeleteConfirm>>,
+    btn_query: Query<(Entity, &ButtonAction)>,
+    mut detail_params: DetailParams,
+) {
+    events.nav_iter().activated_in_query_foreach_mut(
+        &mut actions,
+        |mut action| match &mut *action {
+            DeleteAction::Open(uid) => {
+                if confirm_query.is_empty() {
+                    let keep_btn = open_delete_confirm(&mut commands, &asset_server, uid);
+                    requests.send(NavRequest::FocusOn(keep_btn));
+                }
+            }
+            DeleteAction::Confirm(uid) => {
+                for entity in confirm_query.iter() {
+                    commands.entity(entity).despawn_recursive();
+                }
+                detail_params
+                    .leaderboard
+                    .update(|leaderboard| {
+                        leaderboard.delete(uid);
+                    })
+                    .expect("failed to delete leaderboard record");
+                close_record_detail(&mut commands, &mut requests, &detail_params);
+                if let Ok(mut list) = list_query.get_single_mut() {
+                    list.set_changed();
+                }
+            }
+            DeleteAction::Cancel => {
+                for entity in confirm_query.iter() {
+                    commands.entity(entity).despawn_recursive();
+                }
+                for (entity, action) in btn_query.iter() {
+                    if let ButtonAction::CloseRecord = action {
+                        requests.send(NavRequest::FocusOn(entity));
+                    }
+                }
+            }
+        },
+    );
+}
+
+fn open_delete_confirm(
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
+    uid: &str,
+) -> Entity {
+    let mut keep_btn = Entity::PLACEHOLDER;
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                ..default()
+            },
+            DeleteConfirm,
+            OnPage,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: ui::px_p(3.0),
+                        padding: UiRect::all(ui::px_p(6.0)),
+                        border: UiRect::all(ui::px_p(0.5)),
+                        ..default()
+                    },
+                    background_color: BG_COLOR.into(),
+                    border_color: MUTE_COLOR.into(),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        "Delete this record?",
+                        TextStyle {
+                            font: asset_server.load(FONT),
+                            font_size: ui::FONT_SIZE,
+                            color: FG_COLOR,
+                        },
+                    ));
+                    parent
+                        .spawn(NodeBundle {
+                            style: Style {
+                                align_items: AlignItems::Center,
+                                column_gap: ui::px_p(4.0),
+                                ..default()
+                            },
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            keep_btn = ui::build_btn(
+                                parent,
+                                asset_server,
+                                (
+                                    DeleteAction::Cancel,
+                                    app::interaction::IaButton,
+                                    Focusable::default(),
+                                ),
+                                Style {
+                                    padding: UiRect::all(ui::px_p(ui::BTN_PADDING)),
+                                    ..default()
+                                },
+                                Some("Keep"),
+                                Some("arrow-left"),
+                            );
+                            ui::build_btn(
+                                parent,
+                                asset_server,
+                                (
+                                    DeleteAction::Confirm(uid.to_string()),
+                                    app::interaction::IaButton,
+                                    Focusable::default(),
+                                ),
+                                Style {
+                                    padding: UiRect::all(ui::px_p(ui::BTN_PADDING)),
+                                    ..default()
+                                },
+                                Some("Delete"),
+                                Some("key_clear"),
+                            );
+                        });
+                });
+        });
+    keep_btn
+}
+
+fn close_record_detail(
+    commands: &mut Commands,
+    requests: &mut EventWriter<NavRequest>,
+    detail_params: &DetailParams,
+) {
+    for entity in detail_params.detail_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    if let Ok(entity) = detail_params.default_focus_query.get_single() {
+        requests.send(NavRequest::FocusOn(entity));
+    }
+}
+
+fn build_record_detail(
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
+    images: &mut Assets<Image>,
+    record: &leaderboard::LeaderboardRecord,
+    is_flagged: bool,
+) -> Entity {
+    let mut close_btn = Entity::PLACEHOLDER;
+    let text_style = TextStyle {
+        font: asset_server.load(FONT),
+        font_size: ui::FONT_SIZE,
+        color: FG_COLOR,
+    };
+    let label_style = TextStyle {
+        color: MUTE_COLOR,
+        ..text_style.clone()
+    };
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                ..default()
+            },
+            RecordDetail,
+            OnPage,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: ui::px_p(3.0),
+                        padding: UiRect::all(ui::px_p(DETAIL_PADDING)),
+                        border: UiRect::all(ui::px_p(0.5)),
+                        ..default()
+                    },
+                    background_color: BG_COLOR.into(),
+                    border_color: MUTE_COLOR.into(),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        record.player_name.as_str(),
+                        TextStyle {
+                            font: asset_server.load(FONT_TITLE),
+                            font_size: ui::FONT_SIZE * PAGE_TITLE_RATIO,
+                            color: FG_COLOR,
+                        },
+                    ));
+                    if is_flagged {
+                        parent.spawn(TextBundle::from_section(
+                            "This record failed verification",
+                            TextStyle {
+                                color: ALERT_COLOR,
+                                ..text_style.clone()
+                            },
+                        ));
+                    }
+                    parent
+                        .spawn((
+                            NodeBundle {
+                                style: Style {
+                                    column_gap: ui::px_p(3.0),
+                                    ..default()
+                                },
+                                ..default()
+                            },
+                            ScreenshotPanel,
+                        ))
+                        .with_children(|parent| {
+                            let screenshots: Vec<Handle<Image>> =
+                                app::screenshot::fetch_leaderboard_screenshot_paths(
+                                    &app::screenshot::fetch_screenshots_dir_path(),
+                                    record.uid(),
+                                )
+                                .into_iter()
+                                .filter_map(|(screenshot_type, _)| {
+                                    app::screenshot::fetch_screenshot_image(
+                                        record.uid(),
+                                        screenshot_type,
+                                    )
+                                })
+                                .map(|image| images.add(image))
+                                .collect();
+                            if screenshots.is_empty() {
+                                parent.spawn(TextBundle::from_section(
+                                    "No screenshots",
+                                    label_style.clone(),
+                                ));
+                            }
+                            for image in screenshots {
+                                parent.spawn((
+                                    ImageBundle {
+                                        style: Style {
+                                            width: Val::Px(SCREENSHOT_W),
+                                            height: Val::Px(SCREENSHOT_H),
+                                            border: UiRect::all(ui::px_p(0.5)),
+                                            ..default()
+                                        },
+                                        image: UiImage::new(image),
+                                        ..default()
+                                    },
+                                    ScreenshotImage,
+                                ));
+                            }
+                        });
+                    let stats = [
+                        ("Score", record.score.to_string()),
+                        (
+                            "Time",
+                            format_value(leaderboard::LeaderboardField::Time, record.time),
+                        ),
+                        ("Match", format!("{:.1}%", record.match_point as f32 / 10.0)),
+                        ("Undo", record.undo_count.to_string()),
+                        ("Repaint", record.repaint_count.to_string()),
+                        ("Alpha", record.max_alpha_count.to_string()),
+                        ("Control", record.max_control_chain.to_string()),
+                        ("Hyper", record.max_hyper_chain.to_string()),
+                    ];
+                    parent
+                        .spawn(NodeBundle {
+                            style: Style {
+                                display: Display::Grid,
+                                grid_template_columns: RepeatedGridTrack::auto(4),
+                                column_gap: ui::px_p(4.0),
+                                row_gap: ui::px_p(1.0),
+                                ..default()
+                            },
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            for (label, value) in stats {
+                                parent.spawn(TextBundle::from_section(label, label_style.clone()));
+                                parent.spawn(TextBundle::from_section(value, text_style.clone()));
+                            }
+                        });
+                    for (label, datetime) in [
+                        ("Started", record.started_at.as_str()),
+                        ("Ended", record.ended_at.as_str()),
+                    ] {
+                        parent
+                            .spawn(NodeBundle {
+                                style: Style {
+                                    column_gap: ui::px_p(3.0),
+                                    ..default()
+                                },
+                                ..default()
+                            })
+                            .with_children(|parent| {
+                                parent.spawn(TextBundle::from_section(label, label_style.clone()));
+                                parent.spawn(TextBundle::from_section(
+                                    format_datetime(datetime),
+                                    text_style.clone(),
+                                ));
+                            });
+                    }
+                    parent.spawn((
+                        TextBundle::from_section(
+                            "",
+                            TextStyle {
+                                color: SECONDARY_COLOR,
+                                ..text_style.clone()
+                            },
+                        ),
+                        DetailMessage,
+                    ));
+                    parent
+                        .spawn(NodeBundle {
+                            style: Style {
+                                align_items: AlignItems::Center,
+                                column_gap: ui::px_p(4.0),
+                                ..default()
+                            },
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            close_btn = ui::build_btn(
+                                parent,
+                                asset_server,
+                                (
+                                    ButtonAction::CloseRecord,
+                                    app::interaction::IaButton,
+                                    Focusable::default(),
+                                ),
+                                Style {
+                                    padding: UiRect::all(ui::px_p(ui::BTN_PADDING)),
+                                    ..default()
+                                },
+                                Some("Back"),
+                                Some("arrow-left"),
+                            );
+                            ui::build_btn(
+                                parent,
+                                asset_server,
+                                (
+                                    ButtonAction::ExportRecord(record.uid().to_string()),
+                                    app::interaction::IaButton,
+                                    Focusable::default(),
+                                ),
+                                Style {
+                                    padding: UiRect::all(ui::px_p(ui::BTN_PADDING)),
+                                    ..default()
+                                },
+                                Some("Export"),
+                                Some("download-simple"),
+                            );
+                            if let Some(seed) = record.seed {
+                                ui::build_btn(
+                                    parent,
+                                    asset_server,
+                                    (
+                                        ButtonAction::ReplayRecord(seed),
+                                        app::interaction::IaButton,
+                                        Focusable::default(),
+                                    ),
+                                    Style {
+                                        padding: UiRect::all(ui::px_p(ui::BTN_PADDING)),
+                                        ..default()
+                                    },
+                                    Some("Replay"),
+                                    Some("play"),
+                                );
+                            }
+                            ui::build_btn(
+                                parent,
+                                asset_server,
+                                (
+                                    DeleteAction::Open(record.uid().to_string()),
+                                    app::interaction::IaButton,
+                                    Focusable::default(),
+                                ),
+                                Style {
+                                    padding: UiRect::all(ui::px_p(ui::BTN_PADDING)),
+                                    ..default()
+                                },
+                                Some("Delete"),
+                                Some("key_clear"),
+                            );
+                        });
+                });
+        });
+    close_btn
+}
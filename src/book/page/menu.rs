@@ -50,14 +50,21 @@ enum ButtonAction {
     Quit,
 }
 
-const MENU_PAGES: [&dyn PageBase; 4] = [
+const MENU_PAGES: [&dyn PageBase; 6] = [
     &game::Page,
+    &leaderboard::Page,
+    &achievement::Page,
     &help::Page,
     &settings::audio::Page,
     &about::main::Page,
 ];
 
-fn page_enter(mut commands: Commands, asset_server: Res<AssetServer>) {
+fn page_enter(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut backup_notice: ResMut<app::backup::BackupNotice>,
+) {
+    let notices = std::mem::take(&mut backup_notice.messages);
     commands
         .spawn((build_page_layout(), OnPage))
         .with_children(|parent| {
@@ -151,6 +158,30 @@ fn page_enter(mut commands: Commands, asset_server: Res<AssetServer>) {
                             );
                         });
                 });
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        bottom: ui::px_p(ui::PAGE_PADDING),
+                        width: Val::Percent(100.0),
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for notice in notices {
+                        parent.spawn(TextBundle::from_section(
+                            notice,
+                            TextStyle {
+                                font: asset_server.load(FONT),
+                                font_size: ui::FONT_SIZE * 0.6,
+                                color: ALERT_COLOR,
+                            },
+                        ));
+                    }
+                });
         });
 }
 
@@ -3,6 +3,7 @@ use bevy_ui_navigation::prelude::*;
 
 pub mod audio;
 pub mod control;
+pub mod data;
 pub mod display;
 
 #[derive(Component, Debug)]
@@ -11,6 +12,11 @@ pub enum ButtonAction {
     AppUiNav,
     Toggle(String),
     PlaySe,
+    ExportData,
+    ImportData,
+    SwitchBundle,
+    EditSyncUrl,
+    SwitchMatchMetric,
 }
 
 pub fn build_settings_nav_bar(
@@ -149,6 +155,34 @@ pub fn build_settings_nav_bar(
                                 );
                             }
                         });
+                    parent
+                        .spawn(NodeBundle {
+                            style: Style {
+                                flex_direction: FlexDirection::Column,
+                                align_items: AlignItems::Center,
+                                justify_content: JustifyContent::End,
+                                row_gap: ui::px_p(1.0),
+                                ..default()
+                            },
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            if page == PageState::SettingsData {
+                                build_current_tab(parent, asset_server, "download-simple");
+                            } else {
+                                ui::build_icon_btn(
+                                    parent,
+                                    asset_server,
+                                    (
+                                        ButtonAction::MoveToPage(PageState::SettingsData),
+                                        app::interaction::IaButton,
+                                        Focusable::default(),
+                                    ),
+                                    Style::default(),
+                                    "download-simple",
+                                );
+                            }
+                        });
                 });
         })
         .id()
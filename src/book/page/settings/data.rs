@@ -0,0 +1,560 @@
+use super::*;
+use crate::{app::anime_effect, app::interaction, app::key_binding, app::ui};
+use bevy::ecs::system::SystemParam;
+use bevy_persistent::prelude::*;
+use bevy_ui_navigation::NavRequestSystem;
+use std::path::PathBuf;
+
+const PAGE_CODE: &str = "settings_data";
+const PAGE_NAME: &str = "Settings";
+const PAGE_ICON: &str = "gear";
+
+pub struct Page;
+
+impl PageBase for Page {
+    fn code(&self) -> &str {
+        PAGE_CODE
+    }
+    fn name(&self) -> &str {
+        PAGE_NAME
+    }
+    fn icon(&self) -> &str {
+        PAGE_ICON
+    }
+    fn state(&self) -> PageState {
+        PageState::SettingsData
+    }
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(self.state()),
+            (
+                interaction::reset_default_focus,
+                ui::clear_ui_canvas,
+                page_enter,
+            ),
+        )
+        .add_systems(
+            Update,
+            (
+                (handle_ui_navigation, interaction::handle_default_focus).after(NavRequestSystem),
+                update_sync_text,
+                update_bundle_text,
+                handle_sync_url_input,
+                update_integrity_text,
+            )
+                .run_if(in_state(self.state())),
+        )
+        .add_systems(
+            OnExit(self.state()),
+            (
+                anime_effect::clear_anime_effect,
+                ui::despawn_ui::<OnPage>,
+                page_exit,
+            ),
+        );
+    }
+}
+
+#[derive(Component)]
+struct OnPage;
+
+#[derive(Component)]
+struct DataMessage;
+
+#[derive(Component)]
+struct SyncText;
+
+#[derive(Component)]
+struct IntegrityText;
+
+#[derive(Component)]
+struct SyncUrlInput {
+    url: String,
+    is_editing: bool,
+}
+
+const MAX_SYNC_URL_LENGTH: usize = 64;
+const SYNC_URL_CURSOR: &str = "_";
+const SYNC_URL_SCHEME: &str = "http://";
+
+#[derive(Component)]
+struct BundlePicker {
+    paths: Vec<PathBuf>,
+    index: usize,
+}
+
+impl BundlePicker {
+    fn load() -> Self {
+        Self {
+            paths: app::export::fetch_bundle_paths(),
+            index: 0,
+        }
+    }
+
+    fn selected(&self) -> Option<&PathBuf> {
+        self.paths.get(self.index)
+    }
+
+    fn switch(&mut self) {
+        if !self.paths.is_empty() {
+            self.index = (self.index + 1) % self.paths.len();
+        }
+    }
+}
+
+fn page_enter(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    settings: Res<Persistent<app::settings::Settings>>,
+    sync_queue: Res<Persistent<app::sync::SyncQueue>>,
+) {
+    commands
+        .spawn((build_page_layout(), OnPage))
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        width: Val::Percent(100.0),
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::SpaceBetween,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    build_game_title(parent, &asset_server);
+                    build_page_title(parent, &asset_server, PAGE_NAME, PAGE_ICON);
+                    parent
+                        .spawn(NodeBundle {
+                            style: Style {
+                                flex_grow: 1.0,
+                                flex_direction: FlexDirection::Column,
+                                align_items: AlignItems::Center,
+                                justify_content: JustifyContent::Center,
+                                row_gap: ui::px_p(3.0),
+                                ..default()
+                            },
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            build_sep_title(parent, &asset_server, "Records", "trophy-fill");
+                            parent
+                                .spawn(NodeBundle {
+                                    style: Style {
+                                        align_items: AlignItems::Center,
+                                        column_gap: ui::px_p(4.0),
+                                        ..default()
+                                    },
+                                    ..default()
+                                })
+                                .with_children(|parent| {
+                                    ui::build_btn(
+                                        parent,
+                                        &asset_server,
+                                        (
+                                            ButtonAction::ExportData,
+                                            app::interaction::IaButton,
+                                            Focusable::default(),
+                                        ),
+                                        Style {
+                                            padding: UiRect::all(ui::px_p(ui::BTN_PADDING)),
+                                            ..default()
+                                        },
+                                        Some("Export"),
+                                        Some("download-simple"),
+                                    );
+                                    ui::build_btn(
+                                        parent,
+                                        &asset_server,
+                                        (
+                                            ButtonAction::ImportData,
+                                            app::interaction::IaButton,
+                                            Focusable::default(),
+                                        ),
+                                        Style {
+                                            padding: UiRect::all(ui::px_p(ui::BTN_PADDING)),
+                                            ..default()
+                                        },
+                                        Some("Import"),
+                                        Some("arrow-fat-up-fill"),
+                                    );
+                                    ui::build_icon_btn(
+                                        parent,
+                                        &asset_server,
+                                        (
+                                            ButtonAction::SwitchBundle,
+                                            app::interaction::IaButton,
+                                            Focusable::default(),
+                                        ),
+                                        Style {
+                                            padding: UiRect::all(ui::px_p(ui::BTN_PADDING)),
+                                            ..default()
+                                        },
+                                        "caret-double-right",
+                                    );
+                                });
+                            let bundle_picker = BundlePicker::load();
+                            parent.spawn((
+                                TextBundle::from_section(
+                                    format_bundle_text(&bundle_picker),
+                                    TextStyle {
+                                        font: asset_server.load(FONT),
+                                        font_size: ui::FONT_SIZE * 0.6,
+                                        color: theme::FG_COLOR,
+                                    },
+                                ),
+                                bundle_picker,
+                            ));
+                            parent.spawn((
+                                TextBundle::from_section(
+                                    format!(
+                                        "Bundles: {}",
+                                        app::export::fetch_export_dir_path().display()
+                                    ),
+                                    TextStyle {
+                                        font: asset_server.load(FONT),
+                                        font_size: ui::FONT_SIZE * 0.6,
+                                        color: theme::MUTE_COLOR,
+                                    },
+                                ),
+                                DataMessage,
+                            ));
+                            parent.spawn((
+                                TextBundle::from_section(
+                                    "",
+                                    TextStyle {
+                                        font: asset_server.load(FONT),
+                                        font_size: ui::FONT_SIZE * 0.6,
+                                        color: theme::ALERT_COLOR,
+                                    },
+                                ),
+                                IntegrityText,
+                            ));
+                            build_sep_title(parent, &asset_server, "Sync", "globe");
+                            ui::build_switch_btn(
+                                parent,
+                                &asset_server,
+                                ButtonAction::Toggle(String::from("sync")),
+                                settings.is_enabled("sync"),
+                            );
+                            build_sync_url_entry(parent, &asset_server, &settings);
+                            parent.spawn((
+                                TextBundle::from_section(
+                                    format_sync_text(&sync_queue),
+                                    TextStyle {
+                                        font: asset_server.load(FONT),
+                                        font_size: ui::FONT_SIZE * 0.6,
+                                        color: theme::MUTE_COLOR,
+                                    },
+                                ),
+                                SyncText,
+                            ));
+                        });
+                    build_settings_nav_bar(parent, &asset_server, PageState::SettingsData);
+                });
+        });
+}
+
+fn page_exit(mut key_binding: ResMut<key_binding::KeyBindingConfig>) {
+    key_binding.mode = key_binding::KeyBindingMode::Navgation;
+}
+
+fn build_sync_url_entry(
+    parent: &mut ChildBuilder,
+    asset_server: &Res<AssetServer>,
+    settings: &app::settings::Settings,
+) {
+    let url = settings.fetch_sync_url().to_string();
+    parent
+        .spawn(NodeBundle {
+            style: Style {
+                align_items: AlignItems::Center,
+                column_gap: ui::px_p(3.0),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        width: ui::px_p(120.0),
+                        padding: UiRect::all(ui::px_p(1.0)),
+                        border: UiRect::bottom(ui::px_p(0.5)),
+                        ..default()
+                    },
+                    border_color: theme::FG_COLOR.into(),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    let input = SyncUrlInput {
+                        url,
+                        is_editing: false,
+                    };
+                    parent.spawn((
+                        TextBundle::from_section(
+                            format_sync_url(&input),
+                            TextStyle {
+                                font: asset_server.load(FONT),
+                                font_size: ui::FONT_SIZE * 0.6,
+                                color: theme::FG_COLOR,
+                            },
+                        ),
+                        input,
+                    ));
+                });
+            ui::build_btn(
+                parent,
+                asset_server,
+                (
+                    ButtonAction::EditSyncUrl,
+                    app::interaction::IaButton,
+                    Focusable::default(),
+                ),
+                Style {
+                    padding: UiRect::all(ui::px_p(ui::BTN_PADDING)),
+                    ..default()
+                },
+                Some("Edit"),
+                Some("link-bold"),
+            );
+        });
+}
+
+#[derive(SystemParam)]
+struct DataParams<'w> {
+    leaderboard: ResMut<'w, Persistent<app::leaderboard::Leaderboard>>,
+    ach_store: ResMut<'w, Persistent<app::achievement::AchievementStore>>,
+    report: ResMut<'w, app::integrity::IntegrityReport>,
+}
+
+#[allow(clippy::too_many_arguments)]
+fn handle_ui_navigation(
+    actions: Query<&ButtonAction>,
+    mut events: EventReader<NavEvent>,
+    mut page_state: ResMut<NextState<PageState>>,
+    mut data_params: DataParams,
+    mut settings: ResMut<Persistent<app::settings::Settings>>,
+    mut switch_btn_query: Query<(&Parent, &mut UiImage, &mut ui::SwitchButton)>,
+    mut message_query: Query<&mut Text, With<DataMessage>>,
+    mut picker_query: Query<&mut BundlePicker>,
+    mut url_query: Query<&mut SyncUrlInput>,
+    mut key_binding: ResMut<key_binding::KeyBindingConfig>,
+    asset_server: Res<AssetServer>,
+) {
+    for entity in events.nav_iter().activated() {
+        let Ok(action) = actions.get(entity) else {
+            continue;
+        };
+        let message = match action {
+            ButtonAction::MoveToPage(state) => {
+                page_state.set(*state);
+                continue;
+            }
+            ButtonAction::Toggle(target) => {
+                settings
+                    .update(|settings| {
+                        settings.toggle(target.as_ref());
+                    })
+                    .expect("failed to update boolean switch");
+                ui::update_switch_btn_value(
+                    entity,
+                    &mut switch_btn_query,
+                    &asset_server,
+                    settings.is_enabled(target),
+                );
+                continue;
+            }
+            ButtonAction::ExportData => {
+                match app::export::export_bundle(
+                    &data_params.leaderboard,
+                    &data_params.ach_store,
+                    &app::screenshot::fetch_screenshots_dir_path(),
+                    None,
+                ) {
+                    Ok(path) => {
+                        for mut picker in picker_query.iter_mut() {
+                            *picker = BundlePicker::load();
+                        }
+                        format!("Exported to {}", path.display())
+                    }
+                    Err(error) => format!("Export failed: {}", error),
+                }
+            }
+            ButtonAction::EditSyncUrl => {
+                let Ok(mut input) = url_query.get_single_mut() else {
+                    continue;
+                };
+                if !input.is_editing {
+                    input.is_editing = true;
+                    key_binding.mode = key_binding::KeyBindingMode::Keyboard;
+                    continue;
+                }
+                let url = input.url.trim().to_string();
+                if !url.is_empty() && !url.starts_with(SYNC_URL_SCHEME) {
+                    format!("Sync URL must start with {}", SYNC_URL_SCHEME)
+                } else {
+                    input.is_editing = false;
+                    input.url = url.clone();
+                    key_binding.mode = key_binding::KeyBindingMode::Navgation;
+                    settings
+                        .update(|settings| {
+                            settings.update_sync_url(&url);
+                        })
+                        .expect("failed to update sync url");
+                    continue;
+                }
+            }
+            ButtonAction::SwitchBundle => {
+                for mut picker in picker_query.iter_mut() {
+                    picker.switch();
+                }
+                continue;
+            }
+            ButtonAction::ImportData => match picker_query
+                .get_single()
+                .ok()
+                .and_then(|picker| picker.selected().cloned())
+            {
+                Some(path) => match app::export::read_bundle(&path).and_then(|bundle| {
+                    app::export::import_bundle(
+                        &bundle,
+                        &mut data_params.leaderboard,
+                        &mut data_params.ach_store,
+                        &mut data_params.report,
+                        &app::screenshot::fetch_screenshots_dir_path(),
+                    )
+                }) {
+                    Ok(result) => format!(
+                        "Imported {} records and {} achievements from {}{}",
+                        result.record_count,
+                        result.achievement_count,
+                        path.display(),
+                        app::export::format_flagged_count(result.flagged_count)
+                    ),
+                    Err(error) => format!("Import failed: {}", error),
+                },
+                None => String::from("No bundle found to import"),
+            },
+            _ => continue,
+        };
+        for mut text in message_query.iter_mut() {
+            text.sections[0].value = message.clone();
+        }
+    }
+}
+
+fn update_sync_text(
+    sync_queue: Res<Persistent<app::sync::SyncQueue>>,
+    mut text_query: Query<&mut Text, With<SyncText>>,
+) {
+    if !sync_queue.is_changed() {
+        return;
+    }
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value = format_sync_text(&sync_queue);
+    }
+}
+
+fn handle_sync_url_input(
+    mut char_events: EventReader<ReceivedCharacter>,
+    key_input: Res<Input<KeyCode>>,
+    mut input_query: Query<(&mut SyncUrlInput, &mut Text)>,
+    btn_query: Query<(&ButtonAction, &Children)>,
+    mut label_query: Query<&mut Text, Without<SyncUrlInput>>,
+) {
+    let Ok((mut input, mut text)) = input_query.get_single_mut() else {
+        char_events.clear();
+        return;
+    };
+    if input.is_editing {
+        let mut url = input.url.clone();
+        for event in char_events.read() {
+            if event.char.is_ascii_graphic() && url.chars().count() < MAX_SYNC_URL_LENGTH {
+                url.push(event.char);
+            }
+        }
+        if key_input.just_pressed(KeyCode::Back) {
+            url.pop();
+        }
+        if url != input.url {
+            input.url = url;
+        }
+    } else {
+        char_events.clear();
+    }
+    if !input.is_changed() {
+        return;
+    }
+    text.sections[0].value = format_sync_url(&input);
+    for (action, children) in btn_query.iter() {
+        if !matches!(action, ButtonAction::EditSyncUrl) {
+            continue;
+        }
+        for child in children.iter() {
+            if let Ok(mut label) = label_query.get_mut(*child) {
+                label.sections[0].value =
+                    String::from(if input.is_editing { "Save" } else { "Edit" });
+            }
+        }
+    }
+}
+
+fn format_sync_url(input: &SyncUrlInput) -> String {
+    if input.is_editing {
+        format!("{}{}", input.url, SYNC_URL_CURSOR)
+    } else if input.url.is_empty() {
+        String::from("Sync URL not set")
+    } else {
+        input.url.clone()
+    }
+}
+
+fn update_bundle_text(mut text_query: Query<(&mut Text, &BundlePicker), Changed<BundlePicker>>) {
+    for (mut text, picker) in text_query.iter_mut() {
+        text.sections[0].value = format_bundle_text(picker);
+    }
+}
+
+fn format_bundle_text(picker: &BundlePicker) -> String {
+    match picker.selected() {
+        Some(path) => format!(
+            "Import from {} ({}/{})",
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or_default(),
+            picker.index + 1,
+            picker.paths.len()
+        ),
+        None => String::from("No bundle found to import"),
+    }
+}
+
+fn update_integrity_text(
+    report: Res<app::integrity::IntegrityReport>,
+    mut text_query: Query<(&mut Text, Ref<IntegrityText>)>,
+) {
+    for (mut text, marker) in text_query.iter_mut() {
+        if !report.is_changed() && !marker.is_added() {
+            continue;
+        }
+        text.sections[0].value = if report.is_empty() {
+            String::new()
+        } else {
+            format!(
+                "{} records and {} achievements failed verification",
+                report.flagged_records.len(),
+                report.flagged_achievements.len()
+            )
+        };
+    }
+}
+
+fn format_sync_text(sync_queue: &app::sync::SyncQueue) -> String {
+    format!(
+        "Queued: {} | Only {} URLs are supported",
+        sync_queue.len(),
+        SYNC_URL_SCHEME
+    )
+}
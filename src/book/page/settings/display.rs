@@ -1,5 +1,5 @@
 use super::*;
-use crate::{app::anime_effect, app::interaction, app::ui};
+use crate::{app::anime_effect, app::interaction, app::shape_match, app::ui};
 use bevy::window::WindowMode;
 use bevy_persistent::prelude::*;
 use bevy_ui_navigation::NavRequestSystem;
@@ -99,6 +99,25 @@ fn page_enter(
                                     settings.is_enabled("fullscreen"),
                                 );
                             }
+                            build_sep_title(parent, &asset_server, "Match Mode", "shapes-fill");
+                            ui::build_btn(
+                                parent,
+                                &asset_server,
+                                (
+                                    ButtonAction::SwitchMatchMetric,
+                                    app::interaction::IaButton,
+                                    Focusable::default(),
+                                ),
+                                Style {
+                                    padding: UiRect::all(ui::px_p(ui::BTN_PADDING)),
+                                    ..default()
+                                },
+                                Some(
+                                    shape_match::fetch_metric_kind(settings.fetch_match_metric())
+                                        .name(),
+                                ),
+                                Some("caret-double-right"),
+                            );
                         });
                     build_settings_nav_bar(parent, &asset_server, PageState::SettingsDisplay);
                 });
@@ -113,6 +132,8 @@ fn handle_ui_navigation(
     mut page_state: ResMut<NextState<PageState>>,
     mut settings: ResMut<Persistent<app::settings::Settings>>,
     mut window_query: Query<&mut Window>,
+    children_query: Query<&Children>,
+    mut label_query: Query<&mut Text>,
     asset_server: Res<AssetServer>,
 ) {
     for event in nav_events.read() {
@@ -144,6 +165,22 @@ fn handle_ui_navigation(
                                         }
                                     }
                                 }
+                                ButtonAction::SwitchMatchMetric => {
+                                    let kind = shape_match::fetch_metric_kind(
+                                        settings.fetch_match_metric(),
+                                    )
+                                    .next();
+                                    settings
+                                        .update(|settings| {
+                                            settings.update_match_metric(kind.code());
+                                        })
+                                        .expect("failed to update match mode");
+                                    for child in children_query.iter_descendants(entity) {
+                                        if let Ok(mut label) = label_query.get_mut(child) {
+                                            label.sections[0].value = String::from(kind.name());
+                                        }
+                                    }
+                                }
                                 ButtonAction::MoveToPage(state) => page_state.set(*state),
                                 _ => (),
                             }
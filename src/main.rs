@@ -6,6 +6,16 @@ use bevy_ui_navigation::prelude::*;
 use gaia_s_pen::{app, book};
 
 fn main() {
+    if let Some(action) = app::export::parse_cli_args(std::env::args()) {
+        match app::export::run_cli_action(action) {
+            Ok(message) => println!("{}", message),
+            Err(error) => {
+                eprintln!("{}", error);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
     App::new()
         .add_systems(Startup, app::startup)
         .add_plugins((